@@ -0,0 +1,199 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, Wallet } from "@coral-xyz/anchor";
+import { MasterChef } from "../target/types/master_chef";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createAccount, createMint, getAssociatedTokenAddressSync, mintTo } from "@solana/spl-token";
+import * as fs from "fs";
+
+// Compute units consumed by each master-chef instruction against a local validator.
+// Prints a table and, when BENCH_OUTPUT is set, writes the numbers there as JSON so two
+// builds can be compared.
+describe("compute units", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = provider.wallet as Wallet;
+  const program = anchor.workspace.MasterChef as Program<MasterChef>;
+  const results: { [instruction: string]: number } = {};
+
+  const masterChef = Keypair.generate();
+  const pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  let rewardMint: PublicKey;
+  let userRewardTokenAccount: PublicKey;
+  const pools: {
+    lpMint: PublicKey;
+    lpTokenAccount: PublicKey;
+    userInfo: PublicKey;
+    vault: (seed: string) => PublicKey;
+  }[] = [];
+
+  async function measure(instruction: string, signature: string) {
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    results[instruction] = transaction!.meta!.computeUnitsConsumed!;
+  }
+
+  function depositAccounts(pool: typeof pools[number]) {
+    return {
+      masterChef: masterChef.publicKey,
+      lpTokenVault: pool.vault("lp_token_vault"),
+      user: payer.publicKey,
+      payer: payer.publicKey,
+      userInfo: pool.userInfo,
+      userLpTokenAccount: pool.lpTokenAccount,
+      allowlistEntry: null,
+      referrerInfo: null,
+      receiptMint: null,
+      userReceiptTokenAccount: null,
+      mintAuthority: null,
+    };
+  }
+
+  before(async () => {
+    rewardMint = await createMint(provider.connection, payer.payer, payer.publicKey, null, 9);
+    userRewardTokenAccount = getAssociatedTokenAddressSync(rewardMint, payer.publicKey);
+    for (let i = 0; i < 4; i++) {
+      const lpMint = await createMint(provider.connection, payer.payer, payer.publicKey, null, 9);
+      const lpTokenAccount = await createAccount(provider.connection, payer.payer, lpMint, payer.publicKey);
+      await mintTo(provider.connection, payer.payer, lpMint, lpTokenAccount, payer.payer, 1000000);
+      pools.push({
+        lpMint,
+        lpTokenAccount,
+        userInfo: pda([payer.publicKey.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()]),
+        vault: (seed: string) => pda([Buffer.from(seed), lpMint.toBuffer(), masterChef.publicKey.toBuffer()]),
+      });
+    }
+  });
+
+  after(async () => {
+    console.table(results);
+    if (process.env.BENCH_OUTPUT) {
+      fs.writeFileSync(process.env.BENCH_OUTPUT, JSON.stringify(results, null, 2));
+    }
+  });
+
+  it("initialize", async () => {
+    const tx = await program.methods
+      .initialize()
+      .accounts({ admin: payer.publicKey, masterChef: masterChef.publicKey })
+      .signers([masterChef])
+      .rpc({ commitment: "confirmed" });
+    await measure("initialize", tx);
+  });
+
+  it("add_pool", async () => {
+    for (const [i, pool] of pools.entries()) {
+      const tx = await program.methods
+        .addPool(rewardMint, pool.lpMint, new anchor.BN(0), new anchor.BN(100))
+        .accounts({
+          admin: payer.publicKey,
+          masterChef: masterChef.publicKey,
+          lpMint: pool.lpMint,
+          rewardMint,
+          rewardTokenVault: pool.vault("reward_token_vault"),
+          rewardTokenVaultAuthority: pool.vault("reward_token_vault_auth"),
+          lpTokenVault: pool.vault("lp_token_vault"),
+          lpTokenVaultAuthority: pool.vault("lp_token_vault_auth"),
+        })
+        .rpc({ commitment: "confirmed" });
+      await mintTo(provider.connection, payer.payer, rewardMint, pool.vault("reward_token_vault"), payer.payer, 1000000000);
+      if (i == 0) {
+        await measure("add_pool", tx);
+      }
+    }
+  });
+
+  it("deposit", async () => {
+    for (const [i, pool] of pools.entries()) {
+      const tx = await program.methods
+        .deposit(pool.lpMint, new anchor.BN(1000), [])
+        .accounts(depositAccounts(pool))
+        .rpc({ commitment: "confirmed" });
+      if (i == 0) {
+        await measure("deposit (new user info)", tx);
+      }
+    }
+    const tx = await program.methods
+      .deposit(pools[0].lpMint, new anchor.BN(1000), [])
+      .accounts(depositAccounts(pools[0]))
+      .rpc({ commitment: "confirmed" });
+    await measure("deposit", tx);
+  });
+
+  it("withdraw", async () => {
+    const pool = pools[0];
+    const tx = await program.methods
+      .withdraw(pool.lpMint, new anchor.BN(500))
+      .accounts({
+        masterChef: masterChef.publicKey,
+        lpTokenVault: pool.vault("lp_token_vault"),
+        lpTokenVaultAuthority: pool.vault("lp_token_vault_auth"),
+        user: payer.publicKey,
+        userInfo: pool.userInfo,
+        userLpTokenAccount: pool.lpTokenAccount,
+        receiptMint: null,
+        userReceiptTokenAccount: null,
+      })
+      .rpc({ commitment: "confirmed" });
+    await measure("withdraw", tx);
+  });
+
+  it("claim_reward", async () => {
+    const pool = pools[0];
+    const claim = () =>
+      program.methods
+        .claimReward(pool.lpMint)
+        .accounts({
+          masterChef: masterChef.publicKey,
+          user: payer.publicKey,
+          payer: payer.publicKey,
+          rewardMint,
+          userRewardTokenAccount,
+          userInfo: pool.userInfo,
+          rewardTokenVault: pool.vault("reward_token_vault"),
+          rewardTokenVaultAuthority: pool.vault("reward_token_vault_auth"),
+          referrerInfo: null,
+          mintAuthority: null,
+          devFundTokenAccount: null,
+        })
+        .rpc({ commitment: "confirmed" });
+    await measure("claim_reward (new token account)", await claim());
+    await measure("claim_reward", await claim());
+  });
+
+  it("update_reward_per_slot", async () => {
+    const tx = await program.methods
+      .updateRewardPerSlot(pools[0].lpMint, new anchor.BN(200))
+      .accounts({ admin: payer.publicKey, masterChef: masterChef.publicKey })
+      .rpc({ commitment: "confirmed" });
+    await measure("update_reward_per_slot", tx);
+  });
+
+  it("deposit_many", async () => {
+    const tx = await program.methods
+      .depositMany(pools.map((pool) => pool.lpMint), pools.map(() => new anchor.BN(100)))
+      .accounts({ masterChef: masterChef.publicKey, user: payer.publicKey })
+      .remainingAccounts(pools.flatMap((pool) => [
+        { pubkey: pool.userInfo, isWritable: true, isSigner: false },
+        { pubkey: pool.lpTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: pool.vault("lp_token_vault"), isWritable: true, isSigner: false },
+      ]))
+      .rpc({ commitment: "confirmed" });
+    await measure(`deposit_many (${pools.length} pools)`, tx);
+  });
+
+  it("claim_all", async () => {
+    const tx = await program.methods
+      .claimAll(pools.map((pool) => pool.lpMint))
+      .accounts({ masterChef: masterChef.publicKey, user: payer.publicKey })
+      .remainingAccounts(pools.flatMap((pool) => [
+        { pubkey: pool.userInfo, isWritable: true, isSigner: false },
+        { pubkey: userRewardTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: pool.vault("reward_token_vault"), isWritable: true, isSigner: false },
+        { pubkey: pool.vault("reward_token_vault_auth"), isWritable: false, isSigner: false },
+      ]))
+      .rpc({ commitment: "confirmed" });
+    await measure(`claim_all (${pools.length} pools)`, tx);
+  });
+});
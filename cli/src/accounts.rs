@@ -0,0 +1,135 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Result};
+use master_chef::{constants::{ACCOUNT_VERSION, ALLOWLIST_SEED, MINT_AUTHORITY_SEED, POSITION_SEED, RECEIPT_MINT_SEED, REFERRER_SEED}, legacy::{migrate_fixed_user_info, MasterChefV0}, state::{MasterChef, PoolVaultType, UserInfo, MAX_POOLS}};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::rpc::RpcClient;
+
+pub fn vault_address(vault_type: PoolVaultType, lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[vault_type.get_seed(), lp_token.as_ref(), master_chef.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn vault_authority_address(vault_type: PoolVaultType, lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[vault_type.get_authority_seed(), lp_token.as_ref(), master_chef.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn user_info_address(user: &Pubkey, lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[user.as_ref(), lp_token.as_ref(), master_chef.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn allowlist_entry_address(user: &Pubkey, lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ALLOWLIST_SEED.as_bytes(), lp_token.as_ref(), master_chef.as_ref(), user.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn referrer_info_address(referrer: &Pubkey, lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[REFERRER_SEED.as_bytes(), referrer.as_ref(), lp_token.as_ref(), master_chef.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn position_info_address(position_mint: &Pubkey, lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POSITION_SEED.as_bytes(), position_mint.as_ref(), lp_token.as_ref(), master_chef.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn receipt_mint_address(lp_token: &Pubkey, master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[RECEIPT_MINT_SEED.as_bytes(), lp_token.as_ref(), master_chef.as_ref()],
+        &master_chef::ID,
+    )
+    .0
+}
+
+pub fn mint_authority_address(master_chef: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MINT_AUTHORITY_SEED.as_bytes(), master_chef.as_ref()], &master_chef::ID).0
+}
+
+/// Reads a MasterChef account of the current layout, or a version 0 one converted the way
+/// `migrate` would convert it (`version` stays 0, `reward_owed` is not known yet).
+pub fn fetch_master_chef(rpc: &RpcClient, address: &Pubkey) -> Result<MasterChef> {
+    let data = rpc
+        .get_account_data(address)?
+        .ok_or_else(|| anyhow!("master chef account {} not found", address))?;
+    if data.len() < 8 || data[..8] != MasterChef::discriminator() {
+        bail!("{} is not a master chef account", address);
+    }
+    let body = &data[8..];
+    if body.len() == MasterChefV0::LEN {
+        let mut master_chef: MasterChef = bytemuck::Zeroable::zeroed();
+        master_chef.admin = Pubkey::try_from(&body[..32])?;
+        for (index, pool) in master_chef.pools.iter_mut().enumerate() {
+            MasterChefV0::read_pool(body, index)
+                .migrate_into(pool)
+                .ok_or_else(|| anyhow!("pool #{} of {} cannot be converted", index, address))?;
+        }
+        return Ok(master_chef);
+    }
+    let size = std::mem::size_of::<MasterChef>();
+    if body.len() < size {
+        bail!("{} has an unknown master chef layout ({} bytes)", address, data.len());
+    }
+    Ok(bytemuck::pod_read_unaligned(&body[..size]))
+}
+
+/// Reward vaults of the initialized pools of a version 0 account, in pool order, which
+/// `migrate` expects as remaining accounts. Empty for any later version.
+pub fn fetch_legacy_reward_vaults(rpc: &RpcClient, address: &Pubkey) -> Result<Vec<Pubkey>> {
+    let data = rpc
+        .get_account_data(address)?
+        .ok_or_else(|| anyhow!("master chef account {} not found", address))?;
+    if data.len() != 8 + MasterChefV0::LEN || data[..8] != MasterChef::discriminator() {
+        return Ok(Vec::new());
+    }
+    Ok((0..MAX_POOLS)
+        .map(|index| MasterChefV0::read_pool(&data[8..], index))
+        .filter(|pool| pool.initialized)
+        .map(|pool| pool.reward_token_vault)
+        .collect())
+}
+
+/// Reads a UserInfo account of `lp_token`'s pool in `master_chef`. Accounts older than
+/// `ACCOUNT_VERSION` keep their reward fields as `I80F48` bits (version 0 ones hold only
+/// those fields, zero padded), which are converted the way `migrate_user_info` would convert
+/// them. `version` stays as it was.
+pub fn fetch_user_info(rpc: &RpcClient, address: &Pubkey, master_chef: &MasterChef, lp_token: &Pubkey) -> Result<Option<UserInfo>> {
+    let data = match rpc.get_account_data(address)? {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let mut user_info = UserInfo::try_deserialize(&mut data.as_slice())?;
+    if user_info.version < ACCOUNT_VERSION {
+        let acc_reward_per_share = match master_chef
+            .pools
+            .iter()
+            .find(|pool| pool.initialized && pool.lp_token == *lp_token)
+        {
+            Some(pool) => pool.acc_reward_per_share,
+            None if user_info.amount == 0 => 0,
+            None => bail!("no pool for lp mint {}", lp_token),
+        };
+        migrate_fixed_user_info(&mut user_info, acc_reward_per_share)
+            .ok_or_else(|| anyhow!("reward fields of {} cannot be converted", address))?;
+    }
+    Ok(Some(user_info))
+}
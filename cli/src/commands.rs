@@ -0,0 +1,872 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anyhow::{anyhow, Result};
+use master_chef::{accounts, constants::{ACCOUNT_VERSION, ACC_REWARD_PRECISION}, instruction, state::{EmissionSegment, MasterChefConfig, PoolConfig, PoolGateMode, PoolInfo, PoolRewardMode, PoolVaultType}};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    sysvar,
+    transaction::Transaction,
+};
+use spl_associated_token_account::{get_associated_token_address, instruction::create_associated_token_account_idempotent};
+
+use crate::{accounts::*, Context};
+
+fn send(ctx: &Context, instruction: Instruction, extra_signers: &[&Keypair]) -> Result<()> {
+    send_all(ctx, &[instruction], extra_signers)
+}
+
+fn send_all(ctx: &Context, instructions: &[Instruction], extra_signers: &[&Keypair]) -> Result<()> {
+    let mut signers: Vec<&Keypair> = vec![ctx.payer()?];
+    signers.extend_from_slice(extra_signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer()?.pubkey()),
+        &signers,
+        ctx.rpc.get_latest_blockhash()?,
+    );
+    let signature = ctx.rpc.send_and_confirm_transaction(&transaction)?;
+    println!("Signature: {}", signature);
+    Ok(())
+}
+
+fn ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: master_chef::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(ctx: &Context, master_chef: Keypair) -> Result<()> {
+    send(
+        ctx,
+        ix(
+            accounts::Initialize {
+                admin: ctx.payer()?.pubkey(),
+                master_chef: master_chef.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::Initialize {},
+        ),
+        &[&master_chef],
+    )?;
+    println!("MasterChef: {}", master_chef.pubkey());
+    Ok(())
+}
+
+pub fn add_pool(ctx: &Context, lp_mint: Pubkey, reward_mint: Pubkey, start_slot: u64, reward_per_slot: u64) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    send(
+        ctx,
+        ix(
+            accounts::AddPool {
+                admin: ctx.payer()?.pubkey(),
+                master_chef,
+                lp_mint,
+                reward_mint,
+                lp_token_vault_authority: vault_authority_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                reward_token_vault_authority: vault_authority_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                reward_token_vault: vault_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                rent: sysvar::rent::ID,
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::AddPool {
+                reward_token: reward_mint,
+                lp_token: lp_mint,
+                start_slot,
+                reward_per_slot,
+            },
+        ),
+        &[],
+    )
+}
+
+pub fn set_rate(ctx: &Context, lp_mint: Pubkey, reward_per_slot: u64) -> Result<()> {
+    send(
+        ctx,
+        ix(
+            accounts::UpadteRewardPerSlot {
+                admin: ctx.payer()?.pubkey(),
+                master_chef: ctx.master_chef()?,
+            },
+            instruction::UpdateRewardPerSlot {
+                lp_token: lp_mint,
+                new_reward_per_slot: reward_per_slot,
+            },
+        ),
+        &[],
+    )
+}
+
+fn configure_pool(ctx: &Context, lp_mint: Pubkey, config: PoolConfig) -> Result<()> {
+    send(
+        ctx,
+        ix(
+            accounts::ConfigurePool {
+                admin: ctx.payer()?.pubkey(),
+                master_chef: ctx.master_chef()?,
+            },
+            instruction::ConfigurePool { lp_token: lp_mint, config },
+        ),
+        &[],
+    )
+}
+
+pub fn set_caps(ctx: &Context, lp_mint: Pubkey, max_total_deposit: Option<u64>, max_deposit_per_user: Option<u64>) -> Result<()> {
+    configure_pool(
+        ctx,
+        lp_mint,
+        PoolConfig { max_total_deposit, max_deposit_per_user, ..Default::default() },
+    )
+}
+
+pub fn set_bonus(ctx: &Context, lp_mint: Pubkey, bonus_end_slot: u64, bonus_multiplier: u64) -> Result<()> {
+    configure_pool(
+        ctx,
+        lp_mint,
+        PoolConfig {
+            bonus_end_slot: Some(bonus_end_slot),
+            bonus_multiplier: Some(bonus_multiplier),
+            ..Default::default()
+        },
+    )
+}
+
+pub fn create_receipt_mint(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let receipt_mint = receipt_mint_address(&lp_mint, &master_chef);
+    send(
+        ctx,
+        ix(
+            accounts::CreateReceiptMint {
+                admin: ctx.payer()?.pubkey(),
+                master_chef,
+                lp_mint,
+                receipt_mint,
+                mint_authority: mint_authority_address(&master_chef),
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CreateReceiptMint { lp_token: lp_mint },
+        ),
+        &[],
+    )?;
+    println!("Receipt mint: {}", receipt_mint);
+    Ok(())
+}
+
+pub fn set_gate(ctx: &Context, lp_mint: Pubkey, gate_mode: PoolGateMode, merkle_root: Option<Pubkey>) -> Result<()> {
+    configure_pool(
+        ctx,
+        lp_mint,
+        PoolConfig {
+            gate_mode: Some(gate_mode),
+            merkle_root: merkle_root.map(|root| root.to_bytes()),
+            ..Default::default()
+        },
+    )
+}
+
+pub fn set_referral_rate(ctx: &Context, lp_mint: Pubkey, referral_bps: u16) -> Result<()> {
+    configure_pool(
+        ctx,
+        lp_mint,
+        PoolConfig { referral_bps: Some(referral_bps), ..Default::default() },
+    )
+}
+
+pub fn set_unbonding(ctx: &Context, lp_mint: Pubkey, unbonding_slots: u64) -> Result<()> {
+    configure_pool(
+        ctx,
+        lp_mint,
+        PoolConfig { unbonding_slots: Some(unbonding_slots), ..Default::default() },
+    )
+}
+
+pub fn allowlist(ctx: &Context, lp_mint: Pubkey, user: Pubkey, remove: bool) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let admin = ctx.payer()?.pubkey();
+    let allowlist_entry = allowlist_entry_address(&user, &lp_mint, &master_chef);
+    let instruction = if remove {
+        ix(
+            accounts::RemoveFromAllowlist { admin, master_chef, allowlist_entry },
+            instruction::RemoveFromAllowlist { lp_token: lp_mint, user },
+        )
+    } else {
+        ix(
+            accounts::AddToAllowlist { admin, master_chef, allowlist_entry, system_program: system_program::ID },
+            instruction::AddToAllowlist { lp_token: lp_mint, user },
+        )
+    };
+    send(ctx, instruction, &[])
+}
+
+fn configure(ctx: &Context, config: MasterChefConfig) -> Result<()> {
+    send(
+        ctx,
+        ix(
+            accounts::SetAdmin {
+                admin: ctx.payer()?.pubkey(),
+                master_chef: ctx.master_chef()?,
+            },
+            instruction::SetAdmin { config },
+        ),
+        &[],
+    )
+}
+
+pub fn set_schedule(ctx: &Context, lp_mint: Pubkey, segments: Vec<EmissionSegment>, halving_period: u64) -> Result<()> {
+    send(
+        ctx,
+        ix(
+            accounts::SetEmissionSchedule {
+                admin: ctx.payer()?.pubkey(),
+                master_chef: ctx.master_chef()?,
+            },
+            instruction::SetEmissionSchedule { lp_token: lp_mint, segments, halving_period },
+        ),
+        &[],
+    )
+}
+
+pub fn set_admin(ctx: &Context, new_admin: Pubkey) -> Result<()> {
+    configure(ctx, MasterChefConfig { admin: Some(new_admin), ..Default::default() })
+}
+
+pub fn set_dev_fund(ctx: &Context, dev_fund: Option<Pubkey>, dev_fund_bps: Option<u16>) -> Result<()> {
+    configure(ctx, MasterChefConfig { dev_fund, dev_fund_bps, ..Default::default() })
+}
+
+pub fn set_max_supply(ctx: &Context, max_reward_supply: u64) -> Result<()> {
+    configure(ctx, MasterChefConfig { max_reward_supply: Some(max_reward_supply), ..Default::default() })
+}
+
+pub fn set_reward_mode(ctx: &Context, lp_mint: Pubkey, reward_mode: PoolRewardMode) -> Result<()> {
+    configure_pool(
+        ctx,
+        lp_mint,
+        PoolConfig { reward_mode: Some(reward_mode), ..Default::default() },
+    )
+}
+
+pub fn sweep_dust(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    send(
+        ctx,
+        ix(
+            accounts::SweepRewardDust {
+                admin: ctx.payer()?.pubkey(),
+                master_chef: ctx.master_chef()?,
+            },
+            instruction::SweepRewardDust { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn deposit(
+    ctx: &Context,
+    lp_mint: Pubkey,
+    amount: u64,
+    lp_token_account: Option<Pubkey>,
+    proof: Vec<Pubkey>,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let allowlist_entry = allowlist_entry_address(&user, &lp_mint, &master_chef);
+    let allowlist_entry = ctx.rpc.get_account_data(&allowlist_entry)?.map(|_| allowlist_entry);
+    let (receipt_mint, user_receipt_token_account) = receipt_accounts(ctx, &master_chef, &lp_mint, &user)?;
+    let mut instructions: Vec<Instruction> = receipt_mint
+        .iter()
+        .map(|receipt_mint| create_associated_token_account_idempotent(&user, &user, receipt_mint, &anchor_spl::token::ID))
+        .collect();
+    instructions.push(ix(
+        accounts::Deposit {
+            user,
+            payer: user,
+            master_chef,
+            user_lp_token_account: lp_token_account.unwrap_or_else(|| get_associated_token_address(&user, &lp_mint)),
+            user_info: user_info_address(&user, &lp_mint, &master_chef),
+            lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+            allowlist_entry,
+            referrer_info: referrer.map(|referrer| referrer_info_address(&referrer, &lp_mint, &master_chef)),
+            receipt_mint,
+            user_receipt_token_account,
+            mint_authority: receipt_mint.map(|_| mint_authority_address(&master_chef)),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::Deposit {
+            lp_token: lp_mint,
+            amount,
+            proof: proof.iter().map(|node| node.to_bytes()).collect(),
+        },
+    ));
+    send_all(ctx, &instructions, &[])
+}
+
+pub fn withdraw(ctx: &Context, lp_mint: Pubkey, amount: u64, lp_token_account: Option<Pubkey>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let (receipt_mint, user_receipt_token_account) = receipt_accounts(ctx, &master_chef, &lp_mint, &user)?;
+    send(
+        ctx,
+        ix(
+            accounts::Withdraw {
+                user,
+                master_chef,
+                user_lp_token_account: lp_token_account.unwrap_or_else(|| get_associated_token_address(&user, &lp_mint)),
+                user_info: user_info_address(&user, &lp_mint, &master_chef),
+                lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                lp_token_vault_authority: vault_authority_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                receipt_mint,
+                user_receipt_token_account,
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::Withdraw { lp_token: lp_mint, amount },
+        ),
+        &[],
+    )
+}
+
+pub fn transfer_position(ctx: &Context, lp_mint: Pubkey, recipient: Pubkey, amount: u64, proof: Vec<Pubkey>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let allowlist_entry = allowlist_entry_address(&recipient, &lp_mint, &master_chef);
+    let allowlist_entry = ctx.rpc.get_account_data(&allowlist_entry)?.map(|_| allowlist_entry);
+    let (receipt_mint, user_receipt_token_account) = receipt_accounts(ctx, &master_chef, &lp_mint, &user)?;
+    let (_, recipient_receipt_token_account) = receipt_accounts(ctx, &master_chef, &lp_mint, &recipient)?;
+    let mut instructions: Vec<Instruction> = receipt_mint
+        .iter()
+        .map(|receipt_mint| create_associated_token_account_idempotent(&user, &recipient, receipt_mint, &anchor_spl::token::ID))
+        .collect();
+    instructions.push(ix(
+        accounts::TransferPosition {
+            user,
+            recipient,
+            master_chef,
+            user_info: user_info_address(&user, &lp_mint, &master_chef),
+            recipient_info: user_info_address(&recipient, &lp_mint, &master_chef),
+            allowlist_entry,
+            receipt_mint,
+            user_receipt_token_account,
+            recipient_receipt_token_account,
+            mint_authority: receipt_mint.map(|_| mint_authority_address(&master_chef)),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::TransferPosition {
+            lp_token: lp_mint,
+            amount,
+            proof: proof.iter().map(|node| node.to_bytes()).collect(),
+        },
+    ));
+    send_all(ctx, &instructions, &[])
+}
+
+pub fn request_withdraw(ctx: &Context, lp_mint: Pubkey, amount: u64) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let (receipt_mint, user_receipt_token_account) = receipt_accounts(ctx, &master_chef, &lp_mint, &user)?;
+    send(
+        ctx,
+        ix(
+            accounts::RequestWithdraw {
+                user,
+                master_chef,
+                user_info: user_info_address(&user, &lp_mint, &master_chef),
+                receipt_mint,
+                user_receipt_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            instruction::RequestWithdraw { lp_token: lp_mint, amount },
+        ),
+        &[],
+    )
+}
+
+pub fn complete_withdraw(ctx: &Context, lp_mint: Pubkey, lp_token_account: Option<Pubkey>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    send(
+        ctx,
+        ix(
+            accounts::CompleteWithdraw {
+                user,
+                master_chef,
+                user_lp_token_account: lp_token_account.unwrap_or_else(|| get_associated_token_address(&user, &lp_mint)),
+                user_info: user_info_address(&user, &lp_mint, &master_chef),
+                lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                lp_token_vault_authority: vault_authority_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                token_program: anchor_spl::token::ID,
+            },
+            instruction::CompleteWithdraw { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+fn find_pool<'a>(pools: &'a [PoolInfo], lp_mint: &Pubkey) -> Result<&'a PoolInfo> {
+    pools
+        .iter()
+        .find(|pool| pool.initialized && pool.lp_token == *lp_mint)
+        .ok_or_else(|| anyhow!("no pool for lp mint {}", lp_mint))
+}
+
+/// Receipt mint of a pool and the user's receipt token account, when the pool has a receipt mint.
+fn receipt_accounts(ctx: &Context, master_chef: &Pubkey, lp_mint: &Pubkey, user: &Pubkey) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+    let account = fetch_master_chef(&ctx.rpc, master_chef)?;
+    let pool = find_pool(&account.pools, lp_mint)?;
+    if !pool.has_receipt_mint() {
+        return Ok((None, None));
+    }
+    Ok((Some(pool.receipt_mint), Some(get_associated_token_address(user, &pool.receipt_mint))))
+}
+
+/// Reward mint of a pool, plus the mint authority when the pool mints its rewards and the dev fund
+/// token account when it also mints a dev fund share.
+fn reward_accounts(ctx: &Context, master_chef: &Pubkey, lp_mint: &Pubkey) -> Result<(Pubkey, Option<Pubkey>, Option<Pubkey>)> {
+    let account = fetch_master_chef(&ctx.rpc, master_chef)?;
+    let pool = find_pool(&account.pools, lp_mint)?;
+    if !pool.is_mint_mode() {
+        return Ok((pool.reward_token, None, None));
+    }
+    Ok((
+        pool.reward_token,
+        Some(mint_authority_address(master_chef)),
+        (account.dev_fund_bps > 0).then(|| get_associated_token_address(&account.dev_fund, &pool.reward_token)),
+    ))
+}
+
+pub fn claim(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let (reward_mint, mint_authority, dev_fund_token_account) = reward_accounts(ctx, &master_chef, &lp_mint)?;
+    let user_info = user_info_address(&user, &lp_mint, &master_chef);
+    let account = fetch_master_chef(&ctx.rpc, &master_chef)?;
+    let referrer_info = fetch_user_info(&ctx.rpc, &user_info, &account, &lp_mint)?
+        .filter(|user_info| user_info.has_referrer())
+        .map(|user_info| referrer_info_address(&user_info.referrer, &lp_mint, &master_chef));
+    send(
+        ctx,
+        ix(
+            accounts::ClaimReward {
+                master_chef,
+                user,
+                payer: user,
+                reward_mint,
+                user_reward_token_account: get_associated_token_address(&user, &reward_mint),
+                user_info,
+                referrer_info,
+                reward_token_vault: vault_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                reward_token_vault_authority: vault_authority_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                mint_authority,
+                dev_fund_token_account,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ClaimReward { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn claim_all(ctx: &Context, lp_mints: Vec<Pubkey>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let account = fetch_master_chef(&ctx.rpc, &master_chef)?;
+    let mut instructions = Vec::new();
+    let mut remaining_accounts = Vec::new();
+    for lp_mint in &lp_mints {
+        let reward_mint = find_pool(&account.pools, lp_mint)?.reward_token;
+        let user_reward_token_account = get_associated_token_address(&user, &reward_mint);
+        instructions.push(create_associated_token_account_idempotent(&user, &user, &reward_mint, &anchor_spl::token::ID));
+        remaining_accounts.extend([
+            AccountMeta::new(user_info_address(&user, lp_mint, &master_chef), false),
+            AccountMeta::new(user_reward_token_account, false),
+            AccountMeta::new(vault_address(PoolVaultType::RewardTokenVault, lp_mint, &master_chef), false),
+            AccountMeta::new_readonly(vault_authority_address(PoolVaultType::RewardTokenVault, lp_mint, &master_chef), false),
+        ]);
+    }
+    let mut instruction = ix(
+        accounts::ClaimAll {
+            master_chef,
+            user,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ClaimAll { lp_tokens: lp_mints },
+    );
+    instruction.accounts.extend(remaining_accounts);
+    instructions.push(instruction);
+    send_all(ctx, &instructions, &[])
+}
+
+pub fn deposit_many(ctx: &Context, lp_mints: Vec<Pubkey>, amounts: Vec<u64>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    let mut instruction = ix(
+        accounts::DepositMany {
+            master_chef,
+            user,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::DepositMany { lp_tokens: lp_mints.clone(), amounts },
+    );
+    for lp_mint in &lp_mints {
+        instruction.accounts.extend([
+            AccountMeta::new(user_info_address(&user, lp_mint, &master_chef), false),
+            AccountMeta::new(get_associated_token_address(&user, lp_mint), false),
+            AccountMeta::new(vault_address(PoolVaultType::LPTokenVault, lp_mint, &master_chef), false),
+        ]);
+    }
+    send(ctx, instruction, &[])
+}
+
+pub fn register_referrer(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let referrer = ctx.payer()?.pubkey();
+    send(
+        ctx,
+        ix(
+            accounts::RegisterReferrer {
+                referrer,
+                master_chef,
+                referrer_info: referrer_info_address(&referrer, &lp_mint, &master_chef),
+                system_program: system_program::ID,
+            },
+            instruction::RegisterReferrer { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn claim_referral(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let referrer = ctx.payer()?.pubkey();
+    let (reward_mint, mint_authority, dev_fund_token_account) = reward_accounts(ctx, &master_chef, &lp_mint)?;
+    send(
+        ctx,
+        ix(
+            accounts::ClaimReferralReward {
+                master_chef,
+                referrer,
+                reward_mint,
+                referrer_reward_token_account: get_associated_token_address(&referrer, &reward_mint),
+                referrer_info: referrer_info_address(&referrer, &lp_mint, &master_chef),
+                reward_token_vault: vault_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                reward_token_vault_authority: vault_authority_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                mint_authority,
+                dev_fund_token_account,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ClaimReferralReward { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn close_user(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = ctx.payer()?.pubkey();
+    send(
+        ctx,
+        ix(
+            accounts::CloseUserInfo {
+                user,
+                master_chef,
+                user_info: user_info_address(&user, &lp_mint, &master_chef),
+            },
+            instruction::CloseUserInfo { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn open_position(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let owner = ctx.payer()?.pubkey();
+    let position_mint = Keypair::new();
+    send(
+        ctx,
+        ix(
+            accounts::OpenPosition {
+                owner,
+                master_chef,
+                position_mint: position_mint.pubkey(),
+                position_token_account: get_associated_token_address(&owner, &position_mint.pubkey()),
+                position_info: position_info_address(&position_mint.pubkey(), &lp_mint, &master_chef),
+                mint_authority: mint_authority_address(&master_chef),
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::OpenPosition { lp_token: lp_mint },
+        ),
+        &[&position_mint],
+    )?;
+    println!("Position mint: {}", position_mint.pubkey());
+    Ok(())
+}
+
+pub fn deposit_position(
+    ctx: &Context,
+    lp_mint: Pubkey,
+    position_mint: Pubkey,
+    amount: u64,
+    lp_token_account: Option<Pubkey>,
+    proof: Vec<Pubkey>,
+) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let owner = ctx.payer()?.pubkey();
+    let allowlist_entry = allowlist_entry_address(&owner, &lp_mint, &master_chef);
+    let allowlist_entry = ctx.rpc.get_account_data(&allowlist_entry)?.map(|_| allowlist_entry);
+    send(
+        ctx,
+        ix(
+            accounts::DepositPosition {
+                owner,
+                master_chef,
+                position_mint,
+                position_token_account: get_associated_token_address(&owner, &position_mint),
+                position_info: position_info_address(&position_mint, &lp_mint, &master_chef),
+                user_lp_token_account: lp_token_account.unwrap_or_else(|| get_associated_token_address(&owner, &lp_mint)),
+                allowlist_entry,
+                lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                token_program: anchor_spl::token::ID,
+            },
+            instruction::DepositPosition {
+                lp_token: lp_mint,
+                amount,
+                proof: proof.iter().map(|node| node.to_bytes()).collect(),
+            },
+        ),
+        &[],
+    )
+}
+
+pub fn withdraw_position(
+    ctx: &Context,
+    lp_mint: Pubkey,
+    position_mint: Pubkey,
+    amount: u64,
+    lp_token_account: Option<Pubkey>,
+) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let owner = ctx.payer()?.pubkey();
+    send(
+        ctx,
+        ix(
+            accounts::WithdrawPosition {
+                owner,
+                master_chef,
+                position_mint,
+                position_token_account: get_associated_token_address(&owner, &position_mint),
+                position_info: position_info_address(&position_mint, &lp_mint, &master_chef),
+                user_lp_token_account: lp_token_account.unwrap_or_else(|| get_associated_token_address(&owner, &lp_mint)),
+                lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                lp_token_vault_authority: vault_authority_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                token_program: anchor_spl::token::ID,
+            },
+            instruction::WithdrawPosition { lp_token: lp_mint, amount },
+        ),
+        &[],
+    )
+}
+
+pub fn claim_position(ctx: &Context, lp_mint: Pubkey, position_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let owner = ctx.payer()?.pubkey();
+    let (reward_mint, mint_authority, dev_fund_token_account) = reward_accounts(ctx, &master_chef, &lp_mint)?;
+    send(
+        ctx,
+        ix(
+            accounts::ClaimPositionReward {
+                master_chef,
+                owner,
+                position_mint,
+                position_token_account: get_associated_token_address(&owner, &position_mint),
+                position_info: position_info_address(&position_mint, &lp_mint, &master_chef),
+                reward_mint,
+                owner_reward_token_account: get_associated_token_address(&owner, &reward_mint),
+                reward_token_vault: vault_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                reward_token_vault_authority: vault_authority_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+                mint_authority,
+                dev_fund_token_account,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ClaimPositionReward { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn close_position(ctx: &Context, lp_mint: Pubkey, position_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let owner = ctx.payer()?.pubkey();
+    send(
+        ctx,
+        ix(
+            accounts::ClosePosition {
+                owner,
+                master_chef,
+                position_mint,
+                position_token_account: get_associated_token_address(&owner, &position_mint),
+                position_info: position_info_address(&position_mint, &lp_mint, &master_chef),
+                token_program: anchor_spl::token::ID,
+            },
+            instruction::ClosePosition { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn migrate(ctx: &Context) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let mut instruction = ix(
+        accounts::Migrate {
+            admin: ctx.payer()?.pubkey(),
+            master_chef,
+            system_program: system_program::ID,
+        },
+        instruction::Migrate {},
+    );
+    instruction.accounts.extend(
+        fetch_legacy_reward_vaults(&ctx.rpc, &master_chef)?
+            .into_iter()
+            .map(|reward_token_vault| AccountMeta::new_readonly(reward_token_vault, false)),
+    );
+    send(ctx, instruction, &[])
+}
+
+pub fn migrate_user(ctx: &Context, lp_mint: Pubkey, user: Option<Pubkey>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = match user {
+        Some(user) => user,
+        None => ctx.payer()?.pubkey(),
+    };
+    send(
+        ctx,
+        ix(
+            accounts::MigrateUserInfo {
+                payer: ctx.payer()?.pubkey(),
+                user,
+                master_chef,
+                user_info: user_info_address(&user, &lp_mint, &master_chef),
+                system_program: system_program::ID,
+            },
+            instruction::MigrateUserInfo { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn migrate_position(ctx: &Context, lp_mint: Pubkey, position_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    send(
+        ctx,
+        ix(
+            accounts::MigratePositionInfo {
+                signer: ctx.payer()?.pubkey(),
+                master_chef,
+                position_mint,
+                position_info: position_info_address(&position_mint, &lp_mint, &master_chef),
+            },
+            instruction::MigratePositionInfo { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn audit(ctx: &Context, lp_mint: Pubkey) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    send(
+        ctx,
+        ix(
+            accounts::AuditPool {
+                master_chef,
+                lp_token_vault: vault_address(PoolVaultType::LPTokenVault, &lp_mint, &master_chef),
+                reward_token_vault: vault_address(PoolVaultType::RewardTokenVault, &lp_mint, &master_chef),
+            },
+            instruction::AuditPool { lp_token: lp_mint },
+        ),
+        &[],
+    )
+}
+
+pub fn show_pools(ctx: &Context) -> Result<()> {
+    let master_chef = fetch_master_chef(&ctx.rpc, &ctx.master_chef()?)?;
+    println!("Admin: {}", master_chef.admin);
+    if master_chef.version < ACCOUNT_VERSION {
+        println!("Version: {} (run `migrate` before using it)", master_chef.version);
+    } else {
+        println!("Version: {}", master_chef.version);
+    }
+    println!("Mint authority: {}", mint_authority_address(&ctx.master_chef()?));
+    println!("Dev fund: {} ({} bps)", master_chef.dev_fund, master_chef.dev_fund_bps);
+    println!("Max reward supply: {}", master_chef.max_reward_supply);
+    for (index, pool) in master_chef.pools.iter().enumerate().filter(|(_, pool)| pool.initialized) {
+        println!();
+        println!("Pool #{}", index);
+        println!("  lp token:             {}", pool.lp_token);
+        println!("  reward token:         {}", pool.reward_token);
+        println!("  lp supply:            {}", pool.lp_supply);
+        println!("  start slot:           {}", pool.start_slot);
+        println!("  reward per slot:      {}", pool.reward_per_slot);
+        println!("  last reward slot:     {}", pool.last_reward_slot);
+        println!("  acc reward per share: {}", pool.acc_reward_per_share);
+        println!("  reward owed:          {}", pool.reward_owed);
+        println!("  referral owed:        {}", pool.referral_owed);
+        println!("  reward remainder:     {} (1e-18 units)", pool.reward_remainder);
+        println!("  reward dust:          {} (1e-18 units, {} swept)", pool.reward_dust, pool.swept_reward_dust);
+        println!("  max total deposit:    {}", pool.max_total_deposit);
+        println!("  max deposit per user: {}", pool.max_deposit_per_user);
+        println!("  gate mode:            {}", pool.gate_mode);
+        println!("  merkle root:          {}", Pubkey::new_from_array(pool.merkle_root));
+        println!("  unbonding slots:      {}", pool.unbonding_slots);
+        println!("  cooling supply:       {}", pool.cooling_supply);
+        println!("  referral bps:         {}", pool.referral_bps);
+        println!("  reward mode:          {}", pool.reward_mode);
+        println!("  halving period:       {}", pool.halving_period);
+        println!("  bonus:                x{} until slot {}", pool.bonus_multiplier.max(1), pool.bonus_end_slot);
+        for segment in &pool.segments[..pool.segment_count as usize] {
+            println!("  segment:              {} per slot from slot {}", segment.reward_per_slot, segment.start_slot);
+        }
+        if pool.has_receipt_mint() {
+            println!("  receipt mint:         {}", pool.receipt_mint);
+        }
+        println!("  lp token vault:       {}", pool.lp_token_vault);
+        println!("  reward token vault:   {}", pool.reward_token_vault);
+    }
+    Ok(())
+}
+
+pub fn show_user(ctx: &Context, lp_mint: Pubkey, user: Option<Pubkey>) -> Result<()> {
+    let master_chef = ctx.master_chef()?;
+    let user = match user {
+        Some(user) => user,
+        None => ctx.payer()?.pubkey(),
+    };
+    let address = user_info_address(&user, &lp_mint, &master_chef);
+    let account = fetch_master_chef(&ctx.rpc, &master_chef)?;
+    let user_info = fetch_user_info(&ctx.rpc, &address, &account, &lp_mint)?
+        .ok_or_else(|| anyhow!("user {} has no position in pool {}", user, lp_mint))?;
+    println!("UserInfo: {}", address);
+    println!("  version:        {}", user_info.version);
+    println!("  amount:         {}", user_info.amount);
+    println!("  paid per share: {}", user_info.reward_per_share_paid);
+    println!("  accrued reward: {}", user_info.accrued_reward / ACC_REWARD_PRECISION);
+    println!("  cooling amount: {}", user_info.cooling_amount);
+    println!("  cooldown end:   {}", user_info.cooldown_end_slot);
+    println!("  referrer:       {}", user_info.referrer);
+    Ok(())
+}
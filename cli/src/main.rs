@@ -0,0 +1,468 @@
+mod accounts;
+mod commands;
+mod rpc;
+
+use std::{cell::OnceCell, path::PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use master_chef::state::{EmissionSegment, PoolGateMode, PoolRewardMode};
+use solana_sdk::{pubkey::Pubkey, signature::{read_keypair_file, Keypair}};
+
+use crate::rpc::RpcClient;
+
+#[derive(Parser)]
+#[command(name = "master-chef", about = "Admin and user tool for the master-chef program")]
+struct Cli {
+    /// JSON-RPC endpoint of the cluster to talk to.
+    #[arg(long, short = 'u', env = "MASTER_CHEF_RPC_URL", default_value = "http://localhost:8899")]
+    url: String,
+
+    /// Keypair file used as fee payer and signer (admin or user depending on the command).
+    /// Read-only commands only need it to default to the signer's own stake.
+    #[arg(long, short = 'k', env = "MASTER_CHEF_KEYPAIR", default_value = "~/.config/solana/id.json")]
+    keypair: PathBuf,
+
+    /// Address of the MasterChef account. Required by every command except `initialize`.
+    #[arg(long, short = 'm', env = "MASTER_CHEF_ADDRESS")]
+    master_chef: Option<Pubkey>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a new MasterChef account owned by the signer.
+    Initialize {
+        /// Keypair file for the new MasterChef account.
+        #[arg(long)]
+        master_chef_keypair: PathBuf,
+    },
+    /// Register a new pool and create its vaults.
+    AddPool {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        reward_mint: Pubkey,
+        #[arg(long, default_value_t = 0)]
+        start_slot: u64,
+        #[arg(long)]
+        reward_per_slot: u64,
+    },
+    /// Change the reward emission rate of a pool.
+    SetRate {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        reward_per_slot: u64,
+    },
+    /// Change the deposit caps of a pool, 0 removes a cap. Omitted caps are left unchanged.
+    SetCaps {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        max_total_deposit: Option<u64>,
+        #[arg(long)]
+        max_deposit_per_user: Option<u64>,
+    },
+    /// Set the share of claims credited to referrers, in basis points.
+    SetReferralRate {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        bps: u16,
+    },
+    /// Set the number of slots withdrawals of a pool have to cool down, 0 allows direct withdrawals.
+    SetUnbonding {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        slots: u64,
+    },
+    /// Multiply the emissions of a pool until a given slot.
+    SetBonus {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        end_slot: u64,
+        #[arg(long)]
+        multiplier: u64,
+    },
+    /// Create the receipt mint of an empty pool, minted 1:1 on deposit and burned on withdrawal.
+    CreateReceiptMint {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Restrict who can deposit into a pool.
+    SetGate {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long, value_enum)]
+        mode: GateMode,
+        /// Root of the depositor Merkle tree, base58 encoded.
+        #[arg(long)]
+        merkle_root: Option<Pubkey>,
+    },
+    /// Add a user to (or remove them from) the allowlist of a pool.
+    Allowlist {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        user: Pubkey,
+        #[arg(long)]
+        remove: bool,
+    },
+    /// Replace the emission schedule of a pool.
+    SetSchedule {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        /// Rate change as `START_SLOT:REWARD_PER_SLOT`, repeat in increasing slot order.
+        #[arg(long, value_parser = parse_segment)]
+        segment: Vec<EmissionSegment>,
+        /// Slots after which the emission rate halves, 0 disables halvings.
+        #[arg(long, default_value_t = 0)]
+        halving_period: u64,
+    },
+    /// Hand the admin role over to another key.
+    SetAdmin {
+        #[arg(long)]
+        new_admin: Pubkey,
+    },
+    /// Set the dev fund minted alongside rewards of mint mode pools.
+    SetDevFund {
+        /// Owner of the dev fund, which receives into its associated token account.
+        #[arg(long)]
+        owner: Option<Pubkey>,
+        #[arg(long)]
+        bps: Option<u16>,
+    },
+    /// Cap the reward mint supply reachable by mint mode pools, 0 removes the cap.
+    SetMaxSupply {
+        #[arg(long)]
+        max_reward_supply: u64,
+    },
+    /// Switch a pool between paying rewards from its vault and minting them.
+    SetRewardMode {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long, value_enum)]
+        mode: RewardMode,
+    },
+    /// Release the whole tokens of reward dust left by closed accounts from a pool's owed rewards.
+    SweepDust {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Stake LP tokens into a pool.
+    Deposit {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Source token account, defaults to the signer's associated token account.
+        #[arg(long)]
+        lp_token_account: Option<Pubkey>,
+        /// Merkle proof node of a gated pool, base58 encoded. Repeat for each level.
+        #[arg(long)]
+        proof: Vec<Pubkey>,
+        /// Registered referrer to record on the first deposit.
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// Unstake LP tokens from a pool.
+    Withdraw {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Destination token account, defaults to the signer's associated token account.
+        #[arg(long)]
+        lp_token_account: Option<Pubkey>,
+    },
+    /// Move staked LP tokens to another wallet's position in the same pool.
+    TransferPosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        recipient: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Merkle proof node of the recipient in a gated pool, base58 encoded. Repeat for each level.
+        #[arg(long)]
+        proof: Vec<Pubkey>,
+    },
+    /// Start unbonding LP tokens in a pool with an unbonding period.
+    RequestWithdraw {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+    },
+    /// Transfer out LP tokens whose unbonding period is over.
+    CompleteWithdraw {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        /// Destination token account, defaults to the signer's associated token account.
+        #[arg(long)]
+        lp_token_account: Option<Pubkey>,
+    },
+    /// Claim pending rewards of a pool into the signer's associated token account.
+    Claim {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Claim pending rewards of several pools in one transaction. Pools in mint mode or
+    /// paying a referral share are claimed with `claim`.
+    ClaimAll {
+        /// LP mint of a pool. Repeat for each pool.
+        #[arg(long = "lp-mint", required = true)]
+        lp_mints: Vec<Pubkey>,
+    },
+    /// Stake LP tokens from the signer's associated token accounts into several pools the
+    /// signer already staked in.
+    DepositMany {
+        /// LP mint of a pool. Repeat for each pool.
+        #[arg(long = "lp-mint", required = true)]
+        lp_mints: Vec<Pubkey>,
+        /// Amount for the pool at the same position. Repeat for each pool.
+        #[arg(long = "amount", required = true)]
+        amounts: Vec<u64>,
+    },
+    /// Register the signer as a referrer of a pool.
+    RegisterReferrer {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Claim the signer's referral rewards of a pool.
+    ClaimReferral {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Close the signer's empty position account in a pool and reclaim its rent.
+    CloseUser {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Open a transferable staking position and mint its NFT to the signer.
+    OpenPosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Stake LP tokens into a position held by the signer.
+    DepositPosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        position_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Source token account, defaults to the signer's associated token account.
+        #[arg(long)]
+        lp_token_account: Option<Pubkey>,
+        /// Merkle proof node of a gated pool, base58 encoded. Repeat for each level.
+        #[arg(long)]
+        proof: Vec<Pubkey>,
+    },
+    /// Unstake LP tokens from a position held by the signer.
+    WithdrawPosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        position_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Destination token account, defaults to the signer's associated token account.
+        #[arg(long)]
+        lp_token_account: Option<Pubkey>,
+    },
+    /// Claim pending rewards of a position into the signer's associated token account.
+    ClaimPosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        position_mint: Pubkey,
+    },
+    /// Burn the NFT of an empty position and reclaim its rent.
+    ClosePosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        position_mint: Pubkey,
+    },
+    /// Upgrade the MasterChef account to the current layout.
+    Migrate,
+    /// Upgrade a user's position account to the current layout (permissionless).
+    MigrateUser {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        /// Owner of the position, defaults to the signer.
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+    /// Upgrade an NFT position account to the current layout (permissionless).
+    MigratePosition {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        #[arg(long)]
+        position_mint: Pubkey,
+    },
+    /// Check a pool's supply and owed rewards against its vault balances (permissionless).
+    Audit {
+        #[arg(long)]
+        lp_mint: Pubkey,
+    },
+    /// Print every initialized pool of the MasterChef account.
+    ShowPools,
+    /// Print the staking position of a user in a pool.
+    ShowUser {
+        #[arg(long)]
+        lp_mint: Pubkey,
+        /// Owner of the position, defaults to the signer.
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GateMode {
+    Open,
+    Allowlist,
+    Merkle,
+}
+
+impl From<GateMode> for PoolGateMode {
+    fn from(mode: GateMode) -> Self {
+        match mode {
+            GateMode::Open => PoolGateMode::Open,
+            GateMode::Allowlist => PoolGateMode::Allowlist,
+            GateMode::Merkle => PoolGateMode::Merkle,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RewardMode {
+    Vault,
+    Mint,
+}
+
+impl From<RewardMode> for PoolRewardMode {
+    fn from(mode: RewardMode) -> Self {
+        match mode {
+            RewardMode::Vault => PoolRewardMode::Vault,
+            RewardMode::Mint => PoolRewardMode::Mint,
+        }
+    }
+}
+
+pub struct Context {
+    pub rpc: RpcClient,
+    keypair: PathBuf,
+    payer: OnceCell<Keypair>,
+    master_chef: Option<Pubkey>,
+}
+
+impl Context {
+    /// Fee payer and signer, read from `--keypair` on first use.
+    pub fn payer(&self) -> Result<&Keypair> {
+        if let Some(payer) = self.payer.get() {
+            return Ok(payer);
+        }
+        let payer = read_keypair(&self.keypair)?;
+        Ok(self.payer.get_or_init(|| payer))
+    }
+
+    pub fn master_chef(&self) -> Result<Pubkey> {
+        self.master_chef
+            .ok_or_else(|| anyhow!("--master-chef (or MASTER_CHEF_ADDRESS) is required for this command"))
+    }
+}
+
+fn parse_segment(value: &str) -> Result<EmissionSegment> {
+    let (start_slot, reward_per_slot) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected START_SLOT:REWARD_PER_SLOT, got {}", value))?;
+    Ok(EmissionSegment { start_slot: start_slot.parse()?, reward_per_slot: reward_per_slot.parse()? })
+}
+
+fn read_keypair(path: &std::path::Path) -> Result<Keypair> {
+    let path = match path.strip_prefix("~") {
+        Ok(rest) => PathBuf::from(std::env::var("HOME")?).join(rest),
+        Err(_) => path.to_path_buf(),
+    };
+    read_keypair_file(&path).map_err(|err| anyhow!("failed to read keypair {}: {}", path.display(), err))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context {
+        rpc: RpcClient::new(cli.url),
+        keypair: cli.keypair,
+        payer: OnceCell::new(),
+        master_chef: cli.master_chef,
+    };
+
+    match cli.command {
+        Command::Initialize { master_chef_keypair } => {
+            commands::initialize(&ctx, read_keypair(&master_chef_keypair)?)
+        }
+        Command::AddPool { lp_mint, reward_mint, start_slot, reward_per_slot } => {
+            commands::add_pool(&ctx, lp_mint, reward_mint, start_slot, reward_per_slot)
+        }
+        Command::SetRate { lp_mint, reward_per_slot } => commands::set_rate(&ctx, lp_mint, reward_per_slot),
+        Command::SetCaps { lp_mint, max_total_deposit, max_deposit_per_user } => {
+            commands::set_caps(&ctx, lp_mint, max_total_deposit, max_deposit_per_user)
+        }
+        Command::SetReferralRate { lp_mint, bps } => commands::set_referral_rate(&ctx, lp_mint, bps),
+        Command::SetUnbonding { lp_mint, slots } => commands::set_unbonding(&ctx, lp_mint, slots),
+        Command::CreateReceiptMint { lp_mint } => commands::create_receipt_mint(&ctx, lp_mint),
+        Command::SetBonus { lp_mint, end_slot, multiplier } => commands::set_bonus(&ctx, lp_mint, end_slot, multiplier),
+        Command::SetGate { lp_mint, mode, merkle_root } => {
+            commands::set_gate(&ctx, lp_mint, mode.into(), merkle_root)
+        }
+        Command::Allowlist { lp_mint, user, remove } => commands::allowlist(&ctx, lp_mint, user, remove),
+        Command::SetSchedule { lp_mint, segment, halving_period } => {
+            commands::set_schedule(&ctx, lp_mint, segment, halving_period)
+        }
+        Command::SetAdmin { new_admin } => commands::set_admin(&ctx, new_admin),
+        Command::SetDevFund { owner, bps } => commands::set_dev_fund(&ctx, owner, bps),
+        Command::SetMaxSupply { max_reward_supply } => commands::set_max_supply(&ctx, max_reward_supply),
+        Command::SetRewardMode { lp_mint, mode } => commands::set_reward_mode(&ctx, lp_mint, mode.into()),
+        Command::SweepDust { lp_mint } => commands::sweep_dust(&ctx, lp_mint),
+        Command::Deposit { lp_mint, amount, lp_token_account, proof, referrer } => {
+            commands::deposit(&ctx, lp_mint, amount, lp_token_account, proof, referrer)
+        }
+        Command::Withdraw { lp_mint, amount, lp_token_account } => {
+            commands::withdraw(&ctx, lp_mint, amount, lp_token_account)
+        }
+        Command::TransferPosition { lp_mint, recipient, amount, proof } => {
+            commands::transfer_position(&ctx, lp_mint, recipient, amount, proof)
+        }
+        Command::RequestWithdraw { lp_mint, amount } => commands::request_withdraw(&ctx, lp_mint, amount),
+        Command::CompleteWithdraw { lp_mint, lp_token_account } => {
+            commands::complete_withdraw(&ctx, lp_mint, lp_token_account)
+        }
+        Command::Claim { lp_mint } => commands::claim(&ctx, lp_mint),
+        Command::ClaimAll { lp_mints } => commands::claim_all(&ctx, lp_mints),
+        Command::DepositMany { lp_mints, amounts } => commands::deposit_many(&ctx, lp_mints, amounts),
+        Command::RegisterReferrer { lp_mint } => commands::register_referrer(&ctx, lp_mint),
+        Command::ClaimReferral { lp_mint } => commands::claim_referral(&ctx, lp_mint),
+        Command::CloseUser { lp_mint } => commands::close_user(&ctx, lp_mint),
+        Command::OpenPosition { lp_mint } => commands::open_position(&ctx, lp_mint),
+        Command::DepositPosition { lp_mint, position_mint, amount, lp_token_account, proof } => {
+            commands::deposit_position(&ctx, lp_mint, position_mint, amount, lp_token_account, proof)
+        }
+        Command::WithdrawPosition { lp_mint, position_mint, amount, lp_token_account } => {
+            commands::withdraw_position(&ctx, lp_mint, position_mint, amount, lp_token_account)
+        }
+        Command::ClaimPosition { lp_mint, position_mint } => commands::claim_position(&ctx, lp_mint, position_mint),
+        Command::ClosePosition { lp_mint, position_mint } => commands::close_position(&ctx, lp_mint, position_mint),
+        Command::Migrate => commands::migrate(&ctx),
+        Command::MigrateUser { lp_mint, user } => commands::migrate_user(&ctx, lp_mint, user),
+        Command::MigratePosition { lp_mint, position_mint } => commands::migrate_position(&ctx, lp_mint, position_mint),
+        Command::Audit { lp_mint } => commands::audit(&ctx, lp_mint),
+        Command::ShowPools => commands::show_pools(&ctx),
+        Command::ShowUser { lp_mint, user } => commands::show_user(&ctx, lp_mint, user),
+    }
+}
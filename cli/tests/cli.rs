@@ -0,0 +1,188 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    process::{Command, Output},
+    thread,
+};
+
+use anchor_lang::Discriminator;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use master_chef::{
+    legacy::{MasterChefV0, PoolInfoV0, WrappedI80F48},
+    state::{MasterChef, UserInfo, MAX_POOLS},
+};
+use serde_json::{json, Value};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{write_keypair_file, Keypair, Signer},
+};
+
+/// Nothing listens there, so every RPC call fails right away.
+const UNREACHABLE_URL: &str = "http://127.0.0.1:1";
+
+const MISSING_KEYPAIR: &str = "/nonexistent/id.json";
+
+fn master_chef(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_master-chef"))
+        .env_remove("MASTER_CHEF_RPC_URL")
+        .env_remove("MASTER_CHEF_KEYPAIR")
+        .env_remove("MASTER_CHEF_ADDRESS")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    assert!(!output.status.success());
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// Answers `getAccountInfo` for `accounts` over HTTP and returns the URL to reach it.
+fn serve_accounts(accounts: HashMap<String, Vec<u8>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut reader = BufReader::new(stream.unwrap());
+            loop {
+                let mut content_length = None;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap_or(0) > 0 && line != "\r\n" {
+                    let lower = line.to_ascii_lowercase();
+                    if let Some(length) = lower.strip_prefix("content-length:") {
+                        content_length = length.trim().parse::<usize>().ok();
+                    }
+                    line.clear();
+                }
+                let Some(content_length) = content_length else { break };
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                let value = match accounts.get(request["params"][0].as_str().unwrap()) {
+                    Some(data) => json!({ "data": [STANDARD.encode(data), "base64"] }),
+                    None => Value::Null,
+                };
+                let response = json!({ "jsonrpc": "2.0", "id": 1, "result": { "value": value } }).to_string();
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                    response.len(),
+                    response
+                )
+                .unwrap();
+            }
+        }
+    });
+    url
+}
+
+fn i80f48(value: f64) -> WrappedI80F48 {
+    WrappedI80F48 { value: (value * (1u64 << 48) as f64) as i128 }
+}
+
+#[test]
+fn reads_version_0_accounts() {
+    let address = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let lp_mint = Pubkey::new_unique();
+
+    // version 0 MasterChef, with a pool at 1.5 reward per share
+    let mut pool: PoolInfoV0 = bytemuck::Zeroable::zeroed();
+    pool.lp_token = lp_mint;
+    pool.initialized = true;
+    pool.acc_reward_per_share = i80f48(1.5);
+    let mut master_chef_data = MasterChef::discriminator().to_vec();
+    master_chef_data.extend_from_slice(Pubkey::new_unique().as_ref());
+    master_chef_data.resize(8 + MasterChefV0::LEN - MAX_POOLS * std::mem::size_of::<PoolInfoV0>(), 0);
+    master_chef_data.extend_from_slice(bytemuck::bytes_of(&pool));
+    master_chef_data.resize(8 + MasterChefV0::LEN, 0);
+
+    // version 0 UserInfo: 100 staked at 1.0 per share and 7.25 accrued, in an account
+    // sized like a MasterChef
+    let mut user_info_data = UserInfo::discriminator().to_vec();
+    user_info_data.extend_from_slice(&100u64.to_le_bytes());
+    user_info_data.extend_from_slice(&i80f48(100.0).value.to_le_bytes());
+    user_info_data.extend_from_slice(&i80f48(7.25).value.to_le_bytes());
+    user_info_data.resize(8 + MasterChefV0::LEN, 0);
+
+    let (user_info_address, _) = Pubkey::find_program_address(
+        &[user.as_ref(), lp_mint.as_ref(), address.as_ref()],
+        &master_chef::ID,
+    );
+    let url = serve_accounts(HashMap::from([
+        (address.to_string(), master_chef_data),
+        (user_info_address.to_string(), user_info_data),
+    ]));
+
+    let output = master_chef(&[
+        "-u", &url, "-k", MISSING_KEYPAIR, "-m", &address.to_string(),
+        "show-user", "--lp-mint", &lp_mint.to_string(), "--user", &user.to_string(),
+    ]);
+    let shown = stdout(&output);
+    assert!(shown.contains("version:        0"), "{}", shown);
+    assert!(shown.contains("amount:         100"), "{}", shown);
+    assert!(shown.contains("paid per share: 1000000000000000000\n"), "{}", shown);
+    assert!(shown.contains("accrued reward: 7\n"), "{}", shown);
+}
+
+#[test]
+fn read_only_commands_do_not_read_the_keypair() {
+    let address = Pubkey::new_unique().to_string();
+    let user = Pubkey::new_unique().to_string();
+    let lp_mint = Pubkey::new_unique().to_string();
+
+    let output = master_chef(&["-u", UNREACHABLE_URL, "-k", MISSING_KEYPAIR, "-m", &address, "show-pools"]);
+    let err = stderr(&output);
+    assert!(err.contains("getAccountInfo request"), "{}", err);
+    assert!(!err.contains("keypair"), "{}", err);
+
+    let output = master_chef(&[
+        "-u", UNREACHABLE_URL, "-k", MISSING_KEYPAIR, "-m", &address,
+        "show-user", "--lp-mint", &lp_mint, "--user", &user,
+    ]);
+    let err = stderr(&output);
+    assert!(!err.contains("keypair"), "{}", err);
+}
+
+#[test]
+fn signing_commands_require_the_keypair() {
+    let address = Pubkey::new_unique().to_string();
+    let lp_mint = Pubkey::new_unique().to_string();
+
+    let output = master_chef(&[
+        "-u", UNREACHABLE_URL, "-k", MISSING_KEYPAIR, "-m", &address,
+        "claim", "--lp-mint", &lp_mint,
+    ]);
+    let err = stderr(&output);
+    assert!(err.contains("failed to read keypair /nonexistent/id.json"), "{}", err);
+}
+
+/// Runs against a local validator with master-chef deployed (`anchor localnet`), paying
+/// with the default Solana keypair. Set `MASTER_CHEF_TEST_RPC_URL` to point elsewhere.
+#[test]
+#[ignore = "needs a local validator with master-chef deployed"]
+fn initializes_and_shows_a_master_chef() {
+    let url = std::env::var("MASTER_CHEF_TEST_RPC_URL").unwrap_or_else(|_| "http://localhost:8899".to_string());
+    let account = Keypair::new();
+    let account_path: PathBuf = std::env::temp_dir().join(format!("master-chef-{}.json", account.pubkey()));
+    write_keypair_file(&account, &account_path).unwrap();
+    let address = account.pubkey().to_string();
+
+    let output = master_chef(&["-u", &url, "initialize", "--master-chef-keypair", account_path.to_str().unwrap()]);
+    std::fs::remove_file(&account_path).unwrap();
+    assert!(stdout(&output).contains(&format!("MasterChef: {}", address)));
+
+    // read-only, so a missing keypair does not matter
+    let output = master_chef(&["-u", &url, "-k", MISSING_KEYPAIR, "-m", &address, "show-pools"]);
+    let pools = stdout(&output);
+    assert!(pools.contains("Admin: "), "{}", pools);
+    assert!(pools.contains(&format!("Version: {}", master_chef::constants::ACCOUNT_VERSION)), "{}", pools);
+    assert!(!pools.contains("Pool #"), "{}", pools);
+}
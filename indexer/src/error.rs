@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IndexerError {
+    #[error("invalid base64 event payload: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("failed to decode event: {0}")]
+    Decode(#[from] std::io::Error),
+
+    #[error("CSV export failed: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON export failed: {0}")]
+    Json(#[from] serde_json::Error),
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::{ActionKind, Indexer, IndexerError, TvlPoint, UserAction};
+
+#[derive(Serialize)]
+struct UserActionRow<'a> {
+    master_chef: String,
+    lp_token: String,
+    user: String,
+    position: Option<String>,
+    slot: u64,
+    signature: &'a str,
+    kind: ActionKind,
+    amount: u64,
+    balance: u64,
+    settled_reward: Option<u128>,
+    accrued_reward: Option<u128>,
+}
+
+#[derive(Serialize)]
+struct TvlRow<'a> {
+    master_chef: String,
+    lp_token: String,
+    slot: u64,
+    signature: &'a str,
+    tvl: u64,
+}
+
+#[derive(Serialize)]
+struct UserHistoryDocument<'a> {
+    master_chef: String,
+    lp_token: String,
+    user: String,
+    position: Option<String>,
+    actions: &'a [UserAction],
+}
+
+#[derive(Serialize)]
+struct PoolTvlDocument<'a> {
+    master_chef: String,
+    lp_token: String,
+    tvl: &'a [TvlPoint],
+}
+
+#[derive(Serialize)]
+struct IndexDocument<'a> {
+    users: Vec<UserHistoryDocument<'a>>,
+    pools: Vec<PoolTvlDocument<'a>>,
+}
+
+/// Writes one CSV row per user action, across every pool.
+pub fn write_user_histories_csv<W: Write>(indexer: &Indexer, writer: W) -> Result<(), IndexerError> {
+    let mut csv = csv::Writer::from_writer(writer);
+    for (key, actions) in indexer.user_histories() {
+        for action in actions {
+            csv.serialize(UserActionRow {
+                master_chef: key.pool.master_chef.to_string(),
+                lp_token: key.pool.lp_token.to_string(),
+                user: key.user.to_string(),
+                position: key.position.map(|position| position.to_string()),
+                slot: action.slot,
+                signature: &action.signature,
+                kind: action.kind,
+                amount: action.amount,
+                balance: action.balance,
+                settled_reward: action.settled_reward,
+                accrued_reward: action.accrued_reward,
+            })?;
+        }
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Writes one CSV row per TVL change, across every pool.
+pub fn write_tvl_csv<W: Write>(indexer: &Indexer, writer: W) -> Result<(), IndexerError> {
+    let mut csv = csv::Writer::from_writer(writer);
+    for (key, points) in indexer.pool_tvl_series() {
+        for point in points {
+            csv.serialize(TvlRow {
+                master_chef: key.master_chef.to_string(),
+                lp_token: key.lp_token.to_string(),
+                slot: point.slot,
+                signature: &point.signature,
+                tvl: point.tvl,
+            })?;
+        }
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+/// Writes user histories and pool TVL series as a single JSON document.
+pub fn write_json<W: Write>(indexer: &Indexer, writer: W) -> Result<(), IndexerError> {
+    let document = IndexDocument {
+        users: indexer
+            .user_histories()
+            .map(|(key, actions)| UserHistoryDocument {
+                master_chef: key.pool.master_chef.to_string(),
+                lp_token: key.pool.lp_token.to_string(),
+                user: key.user.to_string(),
+                position: key.position.map(|position| position.to_string()),
+                actions,
+            })
+            .collect(),
+        pools: indexer
+            .pool_tvl_series()
+            .map(|(key, tvl)| PoolTvlDocument {
+                master_chef: key.master_chef.to_string(),
+                lp_token: key.lp_token.to_string(),
+                tvl,
+            })
+            .collect(),
+    };
+    serde_json::to_writer_pretty(writer, &document)?;
+    Ok(())
+}
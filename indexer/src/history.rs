@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+use serde::Serialize;
+
+use crate::{parse_logs, IndexerError, MasterChefEvent, TransactionLogs};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PoolKey {
+    pub master_chef: Pubkey,
+    pub lp_token: Pubkey,
+}
+
+/// A wallet's own stake in a pool, or one of the positions it held (keyed by position mint).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserKey {
+    pub pool: PoolKey,
+    pub user: Pubkey,
+    pub position: Option<Pubkey>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionKind {
+    Deposit,
+    Withdraw,
+    Claim,
+    RequestWithdraw,
+    CompleteWithdraw,
+    TransferOut,
+    TransferIn,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserAction {
+    pub slot: u64,
+    pub signature: String,
+    pub kind: ActionKind,
+    pub amount: u64,
+    /// Staked balance of the user after the action.
+    pub balance: u64,
+    /// Reward the action settled into the user's `accrued_reward`, scaled by
+    /// `ACC_REWARD_PRECISION`. `None` when the event does not report it.
+    pub settled_reward: Option<u128>,
+    /// Reward left claimable after the action, on the same scale.
+    pub accrued_reward: Option<u128>,
+}
+
+/// A transaction left out of the histories because its logs could not be decoded.
+#[derive(Debug)]
+pub struct SkippedTransaction {
+    pub slot: u64,
+    pub signature: String,
+    pub error: IndexerError,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TvlPoint {
+    pub slot: u64,
+    pub signature: String,
+    pub tvl: u64,
+}
+
+/// Folds master-chef events into user histories and pool TVL series.
+///
+/// Transactions must be ingested in the order they were confirmed, and only successful
+/// transactions should be fed in since failed ones still carry their log messages.
+#[derive(Debug, Default)]
+pub struct Indexer {
+    users: BTreeMap<UserKey, Vec<UserAction>>,
+    pools: BTreeMap<PoolKey, Vec<TvlPoint>>,
+    skipped: Vec<SkippedTransaction>,
+}
+
+impl Indexer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies the events of a transaction. A transaction whose logs fail to decode is
+    /// skipped as a whole and recorded in [`Indexer::skipped`] so the rest can still be indexed.
+    pub fn ingest(&mut self, transaction: &TransactionLogs) {
+        match parse_logs(&transaction.logs) {
+            Ok(events) => {
+                for event in events {
+                    self.apply(transaction.slot, &transaction.signature, event);
+                }
+            }
+            Err(error) => self.skipped.push(SkippedTransaction {
+                slot: transaction.slot,
+                signature: transaction.signature.clone(),
+                error,
+            }),
+        }
+    }
+
+    pub fn apply(&mut self, slot: u64, signature: &str, event: MasterChefEvent) {
+        match event {
+            MasterChefEvent::AddPool(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                self.pools.entry(pool).or_default().push(TvlPoint {
+                    slot,
+                    signature: signature.to_string(),
+                    tvl: 0,
+                });
+            }
+            MasterChefEvent::Deposit(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::Deposit,
+                    amount: event.amount,
+                    balance: event.user_amount,
+                    settled_reward: Some(event.settled_reward),
+                    accrued_reward: Some(event.accrued_reward),
+                };
+                self.record_action(pool, event.header.signer, event.position, action);
+                self.record_tvl(pool, slot, signature, event.lp_supply);
+            }
+            MasterChefEvent::Withdraw(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::Withdraw,
+                    amount: event.amount,
+                    balance: event.user_amount,
+                    settled_reward: Some(event.settled_reward),
+                    accrued_reward: Some(event.accrued_reward),
+                };
+                self.record_action(pool, event.header.signer, event.position, action);
+                self.record_tvl(pool, slot, signature, event.lp_supply);
+            }
+            MasterChefEvent::ClaimReward(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::Claim,
+                    amount: event.amount,
+                    balance: event.user_amount,
+                    settled_reward: Some(event.settled_reward),
+                    accrued_reward: Some(event.accrued_reward),
+                };
+                self.record_action(pool, event.header.signer, event.position, action);
+            }
+            // legacy events carry the amount only, so the balance and TVL are carried forward
+            // from the previous action and point
+            MasterChefEvent::LegacyDeposit(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::Deposit,
+                    amount: event.amount,
+                    balance: self.last_balance(pool, event.header.signer).saturating_add(event.amount),
+                    settled_reward: None,
+                    accrued_reward: None,
+                };
+                self.record_action(pool, event.header.signer, None, action);
+                self.record_tvl(pool, slot, signature, self.last_tvl(pool).saturating_add(event.amount));
+            }
+            MasterChefEvent::LegacyWithdraw(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::Withdraw,
+                    amount: event.amount,
+                    balance: self.last_balance(pool, event.header.signer).saturating_sub(event.amount),
+                    settled_reward: None,
+                    accrued_reward: None,
+                };
+                self.record_action(pool, event.header.signer, None, action);
+                self.record_tvl(pool, slot, signature, self.last_tvl(pool).saturating_sub(event.amount));
+            }
+            MasterChefEvent::LegacyClaimReward(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::Claim,
+                    amount: event.amount,
+                    balance: self.last_balance(pool, event.header.signer),
+                    settled_reward: None,
+                    accrued_reward: None,
+                };
+                self.record_action(pool, event.header.signer, None, action);
+            }
+            MasterChefEvent::WithdrawRequested(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::RequestWithdraw,
+                    amount: event.amount,
+                    balance: event.user_amount,
+                    settled_reward: Some(event.settled_reward),
+                    accrued_reward: Some(event.accrued_reward),
+                };
+                self.record_action(pool, event.header.signer, None, action);
+                self.record_tvl(pool, slot, signature, event.lp_supply);
+            }
+            MasterChefEvent::WithdrawCompleted(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                // cooling tokens earn nothing, so completing settles no reward
+                let accrued_reward = self.last_accrued_reward(pool, event.header.signer);
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::CompleteWithdraw,
+                    amount: event.amount,
+                    balance: event.user_amount,
+                    settled_reward: Some(0),
+                    accrued_reward,
+                };
+                self.record_action(pool, event.header.signer, None, action);
+            }
+            MasterChefEvent::TransferPosition(event) => {
+                let pool = PoolKey { master_chef: event.header.master_chef, lp_token: event.lp_token };
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::TransferOut,
+                    amount: event.amount,
+                    balance: event.user_amount,
+                    settled_reward: Some(event.user_settled_reward),
+                    accrued_reward: Some(event.user_accrued_reward),
+                };
+                self.record_action(pool, event.header.signer, None, action);
+                let action = UserAction {
+                    slot,
+                    signature: signature.to_string(),
+                    kind: ActionKind::TransferIn,
+                    amount: event.amount,
+                    balance: event.recipient_amount,
+                    settled_reward: Some(event.recipient_settled_reward),
+                    accrued_reward: Some(event.recipient_accrued_reward),
+                };
+                self.record_action(pool, Some(event.recipient), None, action);
+            }
+            MasterChefEvent::Initialize(_)
+            | MasterChefEvent::SetAdmin(_)
+            | MasterChefEvent::LegacySetAdmin(_)
+            | MasterChefEvent::UpdateRewardPerSlot(_)
+            | MasterChefEvent::PoolUpdated(_) => {}
+        }
+    }
+
+    fn record_action(&mut self, pool: PoolKey, user: Option<Pubkey>, position: Option<Pubkey>, action: UserAction) {
+        if let Some(user) = user {
+            self.users.entry(UserKey { pool, user, position }).or_default().push(action);
+        }
+    }
+
+    fn last_balance(&self, pool: PoolKey, user: Option<Pubkey>) -> u64 {
+        user.and_then(|user| self.users.get(&UserKey { pool, user, position: None })?.last())
+            .map_or(0, |action| action.balance)
+    }
+
+    fn last_tvl(&self, pool: PoolKey) -> u64 {
+        self.pools.get(&pool).and_then(|series| series.last()).map_or(0, |point| point.tvl)
+    }
+
+    fn last_accrued_reward(&self, pool: PoolKey, user: Option<Pubkey>) -> Option<u128> {
+        let user = user?;
+        self.users.get(&UserKey { pool, user, position: None })?.last()?.accrued_reward
+    }
+
+    /// Appends a TVL point unless the supply did not change since the last one.
+    fn record_tvl(&mut self, pool: PoolKey, slot: u64, signature: &str, tvl: u64) {
+        let series = self.pools.entry(pool).or_default();
+        if series.last().is_none_or(|point| point.tvl != tvl) {
+            series.push(TvlPoint { slot, signature: signature.to_string(), tvl });
+        }
+    }
+
+    pub fn user_history(&self, key: &UserKey) -> &[UserAction] {
+        self.users.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn user_histories(&self) -> impl Iterator<Item = (&UserKey, &[UserAction])> {
+        self.users.iter().map(|(key, actions)| (key, actions.as_slice()))
+    }
+
+    pub fn tvl_series(&self, key: &PoolKey) -> &[TvlPoint] {
+        self.pools.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn pool_tvl_series(&self) -> impl Iterator<Item = (&PoolKey, &[TvlPoint])> {
+        self.pools.iter().map(|(key, points)| (key, points.as_slice()))
+    }
+
+    pub fn skipped(&self) -> &[SkippedTransaction] {
+        &self.skipped
+    }
+}
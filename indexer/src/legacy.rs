@@ -0,0 +1,39 @@
+//! Event layouts logged by earlier program versions, before user events carried the state of
+//! the stake after the action. They share their names, and so their discriminators, with the
+//! current events and are only tried once the current layout fails to decode.
+
+use anchor_lang::prelude::*;
+use master_chef::events::EventHeader;
+
+/// Admin only, the dev fund and reward supply settings came later.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MasterChefConfig {
+    pub admin: Option<Pubkey>,
+}
+
+#[event]
+pub struct SetAdminEvent {
+    pub header: EventHeader,
+    pub config: MasterChefConfig,
+}
+
+#[event]
+pub struct DepositEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimRewardEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,15 @@
+//! Off-chain indexer for the master-chef program.
+//!
+//! Transactions are fed in chronological order as raw log messages. Anchor events emitted by
+//! master-chef are decoded from the `Program data:` lines and folded into per-user action
+//! histories and per-pool TVL time series, which can then be exported as CSV or JSON.
+
+pub mod error;
+pub mod export;
+pub mod history;
+pub mod legacy;
+pub mod parser;
+
+pub use error::IndexerError;
+pub use history::{ActionKind, Indexer, PoolKey, SkippedTransaction, TvlPoint, UserAction, UserKey};
+pub use parser::{parse_logs, MasterChefEvent, TransactionLogs};
@@ -0,0 +1,109 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use master_chef::events::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{legacy, IndexerError};
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Log messages of a single confirmed transaction, as returned by `getTransaction`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionLogs {
+    pub slot: u64,
+    pub signature: String,
+    pub logs: Vec<String>,
+}
+
+pub enum MasterChefEvent {
+    Initialize(MasterChefInitializeEvent),
+    SetAdmin(SetAdminEvent),
+    AddPool(AddPoolEvent),
+    UpdateRewardPerSlot(UpadteRewardPerSlotEvent),
+    PoolUpdated(PoolUpdatedEvent),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    ClaimReward(ClaimRewardEvent),
+    WithdrawRequested(WithdrawRequestedEvent),
+    WithdrawCompleted(WithdrawCompletedEvent),
+    TransferPosition(TransferPositionEvent),
+    LegacySetAdmin(legacy::SetAdminEvent),
+    LegacyDeposit(legacy::DepositEvent),
+    LegacyWithdraw(legacy::WithdrawEvent),
+    LegacyClaimReward(legacy::ClaimRewardEvent),
+}
+
+impl MasterChefEvent {
+    /// Decodes a raw event (discriminator followed by the borsh payload).
+    /// Returns `None` for discriminators that do not belong to a known event. Payloads too
+    /// short for the current layout are decoded with the legacy one, if the event has one.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>, IndexerError> {
+        if data.len() < 8 {
+            return Ok(None);
+        }
+        let (discriminator, payload) = data.split_at(8);
+
+        macro_rules! decode_as {
+            ($($variant: ident => $event: ty $(| $legacy_variant: ident => $legacy: ty)?),* $(,)?) => {
+                $(
+                    if discriminator == <$event>::discriminator() {
+                        let event = <$event>::deserialize(&mut &payload[..]).map(Self::$variant);
+                        $(
+                            let event = event.or_else(|error| {
+                                <$legacy>::try_from_slice(payload).map(Self::$legacy_variant).map_err(|_| error)
+                            });
+                        )?
+                        return Ok(Some(event?));
+                    }
+                )*
+            };
+        }
+
+        decode_as!(
+            Initialize => MasterChefInitializeEvent,
+            SetAdmin => SetAdminEvent | LegacySetAdmin => legacy::SetAdminEvent,
+            AddPool => AddPoolEvent,
+            UpdateRewardPerSlot => UpadteRewardPerSlotEvent,
+            PoolUpdated => PoolUpdatedEvent,
+            Deposit => DepositEvent | LegacyDeposit => legacy::DepositEvent,
+            Withdraw => WithdrawEvent | LegacyWithdraw => legacy::WithdrawEvent,
+            ClaimReward => ClaimRewardEvent | LegacyClaimReward => legacy::ClaimRewardEvent,
+            WithdrawRequested => WithdrawRequestedEvent,
+            WithdrawCompleted => WithdrawCompletedEvent,
+            TransferPosition => TransferPositionEvent,
+        );
+        Ok(None)
+    }
+}
+
+/// Extracts every master-chef event from the log messages of a transaction.
+///
+/// The invocation stack is tracked so that `Program data:` lines logged by other programs
+/// (including programs invoked by or invoking master-chef) are ignored.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Result<Vec<MasterChefEvent>, IndexerError> {
+    let program_id = master_chef::ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs.iter().map(AsRef::as_ref) {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if stack.last() == Some(&program_id.as_str()) {
+                for field in data.split_whitespace() {
+                    if let Some(event) = MasterChefEvent::decode(&STANDARD.decode(field)?)? {
+                        events.push(event);
+                    }
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => stack.push(program),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(events)
+}
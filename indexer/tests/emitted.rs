@@ -0,0 +1,377 @@
+//! Builds the replay fixtures from the logs `emit!` writes for each event, and checks the
+//! checked-in files still match. Run with `REGENERATE_FIXTURES=1` to rewrite them after an
+//! event layout changes.
+
+use std::{cell::RefCell, path::Path, str::FromStr, sync::Once};
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::program_stubs::{set_syscall_stubs, SyscallStubs},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use master_chef::{events::*, state::MasterChefConfig};
+use master_chef_indexer::{legacy, TransactionLogs};
+
+const MASTER_CHEF: &str = "FJdM2F7N5o94X7ab1mvBxnWLwJHhP2BU8yfVSmNwNkzm";
+const LP_TOKEN: &str = "EpBLJivcREeg7qUpu3xNB3nFExBgYQAjSFiGJDgD1qdZ";
+const REWARD_TOKEN: &str = "8hp1Fqe9Gtb1dc6zxb9xpPKieLykL1KM4DJLcgycA9AZ";
+const ADMIN: &str = "AT7MMgHMVAvAni3ipu6X6HYs4gXPXJ1kS9sKrGPG1Jtw";
+const NEW_ADMIN: &str = "6a5QaApN2bRiTtHBHZ6dqFLNCaV8BTgncVs2zkEVYSn";
+const USER_ONE: &str = "GdutUNeTqomG3ZYvisrvUyxgyUtKh2AJ5MDzkU5WXDn7";
+const USER_TWO: &str = "6sL2ishCA8gLB67vmKY6CT6j6SZSuZpn9m8F1R46bR8G";
+const POSITION_MINT: &str = "HcycuoevmhH5yUvM8qTj3sBgg58todBF6CiLk57XAGAz";
+/// A program that logs a master-chef event itself before invoking master-chef.
+const OTHER_PROGRAM: &str = "AZPZxh8T1QZ5J3rCJgf3HmeqqKjXi4AFheGNq6Pcv8hy";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+/// Reward scale of the accumulator when the fixtures were recorded.
+const SCALE: u128 = 1 << 48;
+
+thread_local! {
+    static LOGGED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Collects what `sol_log_data` would log, the way the runtime formats it.
+struct LogCapture;
+
+impl SyscallStubs for LogCapture {
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<_> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        LOGGED.with(|logged| logged.borrow_mut().push(format!("Program data: {}", fields.join(" "))));
+    }
+}
+
+fn key(address: &str) -> Pubkey {
+    Pubkey::from_str(address).unwrap()
+}
+
+fn header(signer: Option<&str>) -> EventHeader {
+    EventHeader { master_chef: key(MASTER_CHEF), signer: signer.map(key) }
+}
+
+struct Transaction {
+    logs: Vec<String>,
+}
+
+impl Transaction {
+    fn new() -> Self {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(LogCapture));
+        });
+        Self { logs: Vec::new() }
+    }
+
+    fn invoke(mut self, program: &str, depth: u8) -> Self {
+        self.logs.push(format!("Program {} invoke [{}]", program, depth));
+        self
+    }
+
+    fn instruction(mut self, name: &str) -> Self {
+        self.logs.push(format!("Program log: Instruction: {}", name));
+        self
+    }
+
+    fn token_transfer(self) -> Self {
+        let mut transaction = self.invoke(TOKEN_PROGRAM, 2).instruction("Transfer");
+        transaction.logs.push(format!("Program {} consumed 4645 of 180000 compute units", TOKEN_PROGRAM));
+        transaction.logs.push(format!("Program {} success", TOKEN_PROGRAM));
+        transaction
+    }
+
+    fn emit<E: anchor_lang::Event>(mut self, event: E) -> Self {
+        emit!(event);
+        LOGGED.with(|logged| self.logs.append(&mut logged.borrow_mut()));
+        self
+    }
+
+    fn success(mut self, program: &str, consumed: u64) -> Self {
+        self.logs.push(format!("Program {} consumed {} of 200000 compute units", program, consumed));
+        self.logs.push(format!("Program {} success", program));
+        self
+    }
+
+    fn finish(self, slot: u64, signature: &str) -> TransactionLogs {
+        TransactionLogs { slot, signature: signature.to_string(), logs: self.logs }
+    }
+
+    /// Wraps up a top level master-chef instruction.
+    fn done(self, slot: u64, signature: &str) -> TransactionLogs {
+        self.success(&master_chef::ID.to_string(), 20000).finish(slot, signature)
+    }
+}
+
+fn master_chef_instruction(name: &str) -> Transaction {
+    Transaction::new().invoke(&master_chef::ID.to_string(), 1).instruction(name)
+}
+
+fn pool_updated(slot: u64, last_reward_slot: u64, reward_amount: u64, lp_supply: u64, acc_reward_per_share: u128) -> PoolUpdatedEvent {
+    PoolUpdatedEvent {
+        header: header(None),
+        lp_token: key(LP_TOKEN),
+        slot,
+        last_reward_slot,
+        reward_amount,
+        lp_supply,
+        acc_reward_per_share,
+    }
+}
+
+/// A pool earning 10 and later 20 reward per slot, staked by two wallets and a position.
+fn transactions() -> Vec<TransactionLogs> {
+    let acc_120 = SCALE / 2;
+    let acc_125 = acc_120 + 50 * SCALE / 500;
+    let acc_130 = acc_125 + 100 * SCALE / 500;
+    let acc_140 = acc_130 + 200 * SCALE / 400;
+    let acc_150 = acc_140 + 200 * SCALE / 400;
+    let acc_180 = acc_150 + 300 * SCALE / 300;
+    // what each wallet holds in `accrued_reward` after its last action
+    let user_one_withdraw = 200 * acc_130;
+    let user_one_claim = 100 * (acc_140 - acc_130);
+    let user_one_claimed = (user_one_withdraw + user_one_claim) / SCALE;
+    let user_one_left = user_one_withdraw + user_one_claim - user_one_claimed * SCALE;
+    let user_two_request = 300 * (acc_150 - acc_120);
+
+    vec![
+        master_chef_instruction("Initialize")
+            .emit(MasterChefInitializeEvent { header: header(Some(ADMIN)) })
+            .done(100, "4JiQSypTKWTWMpScuf6ygX3i6XHbLojMRot7n3NmoXzGi5hniyYbRxtcuHQT87Gdrf3BgasZ5aV7Jw2TAwU34W7b"),
+        master_chef_instruction("AddPool")
+            .emit(AddPoolEvent {
+                header: header(Some(ADMIN)),
+                reward_token: key(REWARD_TOKEN),
+                lp_token: key(LP_TOKEN),
+                start_slot: 0,
+                reward_per_slot: 10,
+            })
+            .done(101, "2ama8xh992RpyXt99yiu3JnqLdYwTSksqni28Wia7mdVJr4xYqxhBhPWePuHfmDtZUijyxnWEXSDruiGUgxPwZUt"),
+        master_chef_instruction("Deposit")
+            .token_transfer()
+            .emit(pool_updated(110, 101, 0, 0, 0))
+            .emit(DepositEvent {
+                header: header(Some(USER_ONE)),
+                lp_token: key(LP_TOKEN),
+                amount: 200,
+                user_amount: 200,
+                settled_reward: 0,
+                accrued_reward: 0,
+                lp_supply: 200,
+                acc_reward_per_share: 0,
+                slot: 110,
+                position: None,
+            })
+            .done(110, "46XS923nGDPkDwwFDx2qBDJoxw2CEunW8KSSquakLL6hFjPTgNN5v4ao6MQgCNxeYsPKW9RpLVhtxTWZUKqJhBTP"),
+        Transaction::new()
+            .invoke(OTHER_PROGRAM, 1)
+            .emit(legacy::DepositEvent { header: header(Some(USER_ONE)), lp_token: key(LP_TOKEN), amount: 999_999 })
+            .invoke(&master_chef::ID.to_string(), 2)
+            .instruction("Deposit")
+            .emit(pool_updated(120, 110, 100, 200, acc_120))
+            .emit(DepositEvent {
+                header: header(Some(USER_TWO)),
+                lp_token: key(LP_TOKEN),
+                amount: 300,
+                user_amount: 300,
+                settled_reward: 0,
+                accrued_reward: 0,
+                lp_supply: 500,
+                acc_reward_per_share: acc_120,
+                slot: 120,
+                position: None,
+            })
+            .success(&master_chef::ID.to_string(), 20000)
+            .success(OTHER_PROGRAM, 40000)
+            .finish(120, "3oZUkkYkvnGBWPdnjvNTHAj86zCWhAQyh6TW7hLAMRCAjhc5xkYyPjEi23Aj6527RDUkg2reyFFDq2T7a2K1q2Qr"),
+        master_chef_instruction("UpdateRewardPerSlot")
+            .emit(pool_updated(125, 120, 50, 500, acc_125))
+            .emit(UpadteRewardPerSlotEvent {
+                header: header(Some(ADMIN)),
+                lp_token: key(LP_TOKEN),
+                old_reward_per_slot: 10,
+                new_reward_per_slot: 20,
+            })
+            .done(125, "bXJRop726nEt9755tYTBRqh2RvEyi1Sov9Y6mK1xcKvesMmX4dTWQgNuvwv5viqLYH1CiHusWwLqyTLwVysGhud"),
+        master_chef_instruction("Withdraw")
+            .token_transfer()
+            .emit(pool_updated(130, 125, 100, 500, acc_130))
+            .emit(WithdrawEvent {
+                header: header(Some(USER_ONE)),
+                lp_token: key(LP_TOKEN),
+                amount: 100,
+                user_amount: 100,
+                settled_reward: user_one_withdraw,
+                accrued_reward: user_one_withdraw,
+                lp_supply: 400,
+                acc_reward_per_share: acc_130,
+                slot: 130,
+                position: None,
+            })
+            .done(130, "4BGfACnUze1PKLod2LzxJKLhvYSqyB1uQMTvofMRhW1L4QqU5a2f7anLEVPD7ubmfzLN6FnKgtpq2hJ7ZYyTH9sC"),
+        master_chef_instruction("ClaimReward")
+            .token_transfer()
+            .emit(pool_updated(140, 130, 200, 400, acc_140))
+            .emit(ClaimRewardEvent {
+                header: header(Some(USER_ONE)),
+                lp_token: key(LP_TOKEN),
+                amount: user_one_claimed as u64,
+                user_amount: 100,
+                settled_reward: user_one_claim,
+                accrued_reward: user_one_left,
+                lp_supply: 400,
+                acc_reward_per_share: acc_140,
+                slot: 140,
+                position: None,
+            })
+            .done(140, "nJ25gp5NwzHK9hvYw4WrrtntJoWCzzKUpSCVhXUEmzD1z8QMdvt6jRnYmx6ZyCzTvMQ2DMnYwkQAnEzuyGoDcZB"),
+        master_chef_instruction("RequestWithdraw")
+            .emit(pool_updated(150, 140, 200, 400, acc_150))
+            .emit(WithdrawRequestedEvent {
+                header: header(Some(USER_TWO)),
+                lp_token: key(LP_TOKEN),
+                amount: 100,
+                user_amount: 200,
+                cooling_amount: 100,
+                cooldown_end_slot: 170,
+                settled_reward: user_two_request,
+                accrued_reward: user_two_request,
+                lp_supply: 300,
+                acc_reward_per_share: acc_150,
+                slot: 150,
+            })
+            .done(150, "3bhgtmtNRt8WUZ6g3Ji1RXLCXjz2Yp9zYBVYWhiyNVRTWtDxgiCQp9VA6SSZptS1w5nLKf9Ar4WuyJKzKhxaVktm"),
+        master_chef_instruction("CompleteWithdraw")
+            .token_transfer()
+            .emit(WithdrawCompletedEvent {
+                header: header(Some(USER_TWO)),
+                lp_token: key(LP_TOKEN),
+                amount: 100,
+                user_amount: 200,
+                cooling_supply: 0,
+                slot: 170,
+            })
+            .done(170, "4r8kTHUZAha3U5vWpozC5VKZCPW58wG6FMYLyJGPm5qLbzro1xpEJf1bZEVvwy4LaHvVHp4ZtL2EWbpjqC1NLPjf"),
+        master_chef_instruction("TransferPosition")
+            .emit(pool_updated(180, 150, 300, 300, acc_180))
+            .emit(TransferPositionEvent {
+                header: header(Some(USER_TWO)),
+                lp_token: key(LP_TOKEN),
+                recipient: key(USER_ONE),
+                amount: 50,
+                user_amount: 150,
+                recipient_amount: 150,
+                user_settled_reward: 200 * (acc_180 - acc_150),
+                user_accrued_reward: user_two_request + 200 * (acc_180 - acc_150),
+                recipient_settled_reward: 100 * (acc_180 - acc_140),
+                recipient_accrued_reward: user_one_left + 100 * (acc_180 - acc_140),
+                acc_reward_per_share: acc_180,
+                slot: 180,
+            })
+            .done(180, "yEbk75rDqSkQL2mdzJRC3CAWh1KDMbNTeH65rh3Va7uFt2VU3zLEKaJWRr4CE6V5wYp8cddNrPUWKGNGZx2XtTT"),
+        master_chef_instruction("DepositPosition")
+            .token_transfer()
+            .emit(DepositEvent {
+                header: header(Some(USER_ONE)),
+                lp_token: key(LP_TOKEN),
+                amount: 40,
+                user_amount: 40,
+                settled_reward: 0,
+                accrued_reward: 0,
+                lp_supply: 340,
+                acc_reward_per_share: 0,
+                slot: 190,
+                position: Some(key(POSITION_MINT)),
+            })
+            .done(190, "5KpJeHWScJyPBvMdod2vspuCnrP9aL2frEYLMkuaNondgsbauM47YeGhEKjSK1ZZjXjFkv6gpqyMZXGcbaJfndHf"),
+        master_chef_instruction("SetAdmin")
+            .emit(SetAdminEvent {
+                header: header(Some(ADMIN)),
+                config: MasterChefConfig {
+                    admin: Some(key(NEW_ADMIN)),
+                    dev_fund: None,
+                    dev_fund_bps: None,
+                    max_reward_supply: None,
+                },
+            })
+            .done(200, "2xjpfqs67g5TCu26kyQpS7fjvPNf9Bj1GrnQ8NTfWRRadKuhqz2iDseVbKwF9aTFkDSUEkeGja9wGsSkRSwh2q84"),
+    ]
+}
+
+/// Logs of a program version whose user events carried the amount only, followed by a
+/// deposit made after the upgrade.
+fn legacy_transactions() -> Vec<TransactionLogs> {
+    let acc_60 = 100 * SCALE / 450;
+
+    vec![
+        master_chef_instruction("Initialize")
+            .emit(MasterChefInitializeEvent { header: header(Some(ADMIN)) })
+            .done(10, "24a27et5ZKRFbG9XekQRitrPkh6y1Fgwpd9Mz6PK6pZU8GgFhfk8WSwJMEstSod8pQ1k3X2727LWuocAJQ96Fast"),
+        master_chef_instruction("SetAdmin")
+            .emit(legacy::SetAdminEvent {
+                header: header(Some(ADMIN)),
+                config: legacy::MasterChefConfig { admin: Some(key(NEW_ADMIN)) },
+            })
+            .done(11, "fMvbfhAY2Hcm1B9KPMFEpibp9m3dx3Hx5Ro7WfKR2g3r8YrnTMnskCJhf4ys9K6ZzUW4mny5VXJFkUgdZApqJtR"),
+        master_chef_instruction("AddPool")
+            .emit(AddPoolEvent {
+                header: header(Some(NEW_ADMIN)),
+                reward_token: key(REWARD_TOKEN),
+                lp_token: key(LP_TOKEN),
+                start_slot: 0,
+                reward_per_slot: 10,
+            })
+            .done(12, "3myoN9ZBheHoWhuBK6pSU8fPEYrCGYgR3LMu1LXyp6SL7zyKjSsh8n5G1zboEjXipkw7Lk51G5xeSYUgy8ZbVv8E"),
+        master_chef_instruction("Deposit")
+            .token_transfer()
+            .emit(legacy::DepositEvent { header: header(Some(USER_ONE)), lp_token: key(LP_TOKEN), amount: 200 })
+            .done(20, "TSPEHibWxTNp5B7xJHnHAPZ7EpNZQQ6gweuRvVPeFZYSYogsAMcMGnnzWSEvHW1X3ouGpa7Tg9vyJPDQzMJu16M"),
+        master_chef_instruction("Deposit")
+            .token_transfer()
+            .emit(legacy::DepositEvent { header: header(Some(USER_TWO)), lp_token: key(LP_TOKEN), amount: 300 })
+            .done(30, "4i5S9nwecs41WW734KidxatuiyuTVoc6CWzegAfxnS67z59Au5w5Yq3eSoN23eXZpfviKKsH6Gd9grackvVYZQR5"),
+        master_chef_instruction("Withdraw")
+            .token_transfer()
+            .emit(legacy::WithdrawEvent { header: header(Some(USER_ONE)), lp_token: key(LP_TOKEN), amount: 50 })
+            .done(40, "54CsoD5uLnmAEhkWEweV6cNEiEq78q1zMfjmExu5YkzBh3CSqp6ezVK2TY7n15XDJyRCTYiJSRSMuR43hRAyQC91"),
+        master_chef_instruction("ClaimReward")
+            .token_transfer()
+            .emit(legacy::ClaimRewardEvent { header: header(Some(USER_ONE)), lp_token: key(LP_TOKEN), amount: 25 })
+            .done(50, "5igsD2XrAyTXqGonKSsStUvRaPnguE1SyGUVQFHG1GrLHm8DMTkCzhwLAX2avSK2H626N1C3MR3gABiM9DXu7pzP"),
+        master_chef_instruction("Deposit")
+            .token_transfer()
+            .emit(pool_updated(60, 50, 100, 450, acc_60))
+            .emit(DepositEvent {
+                header: header(Some(USER_TWO)),
+                lp_token: key(LP_TOKEN),
+                amount: 100,
+                user_amount: 400,
+                settled_reward: 300 * acc_60,
+                accrued_reward: 300 * acc_60,
+                lp_supply: 550,
+                acc_reward_per_share: acc_60,
+                slot: 60,
+                position: None,
+            })
+            .done(60, "58BQZp5xUFYjy9NxhwHrgGB1ytKV1FDKCvMWD8M5aB63EpNPcRSYFHgfqtcQq3yech2qpb5wfumtgsfAG9gQYdCu"),
+    ]
+}
+
+fn check_fixture(name: &str, transactions: Vec<TransactionLogs>) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+    let emitted = serde_json::to_string_pretty(&transactions).unwrap() + "\n";
+    if std::env::var_os("REGENERATE_FIXTURES").is_some() {
+        std::fs::write(&path, emitted).unwrap();
+        return;
+    }
+    let checked_in = std::fs::read_to_string(&path).unwrap();
+    assert!(checked_in == emitted, "{} is stale, rerun with REGENERATE_FIXTURES=1", name);
+}
+
+#[test]
+fn fixtures_match_emitted_events() {
+    check_fixture("transactions.json", transactions());
+}
+
+#[test]
+fn legacy_fixtures_match_emitted_events() {
+    check_fixture("legacy_transactions.json", legacy_transactions());
+}
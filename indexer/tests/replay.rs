@@ -0,0 +1,270 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use master_chef_indexer::{
+    export, parse_logs, ActionKind, Indexer, MasterChefEvent, PoolKey, TransactionLogs, UserKey,
+};
+
+const MASTER_CHEF: &str = "FJdM2F7N5o94X7ab1mvBxnWLwJHhP2BU8yfVSmNwNkzm";
+const LP_TOKEN: &str = "EpBLJivcREeg7qUpu3xNB3nFExBgYQAjSFiGJDgD1qdZ";
+const USER_ONE: &str = "GdutUNeTqomG3ZYvisrvUyxgyUtKh2AJ5MDzkU5WXDn7";
+const USER_TWO: &str = "6sL2ishCA8gLB67vmKY6CT6j6SZSuZpn9m8F1R46bR8G";
+const NEW_ADMIN: &str = "6a5QaApN2bRiTtHBHZ6dqFLNCaV8BTgncVs2zkEVYSn";
+const POSITION_MINT: &str = "HcycuoevmhH5yUvM8qTj3sBgg58todBF6CiLk57XAGAz";
+
+fn fixtures() -> Vec<TransactionLogs> {
+    serde_json::from_str(include_str!("fixtures/transactions.json")).unwrap()
+}
+
+fn legacy_fixtures() -> Vec<TransactionLogs> {
+    serde_json::from_str(include_str!("fixtures/legacy_transactions.json")).unwrap()
+}
+
+fn pool() -> PoolKey {
+    PoolKey {
+        master_chef: Pubkey::from_str(MASTER_CHEF).unwrap(),
+        lp_token: Pubkey::from_str(LP_TOKEN).unwrap(),
+    }
+}
+
+fn replay_all(transactions: Vec<TransactionLogs>) -> Indexer {
+    let mut indexer = Indexer::new();
+    for transaction in transactions {
+        indexer.ingest(&transaction);
+    }
+    indexer
+}
+
+fn replay() -> Indexer {
+    replay_all(fixtures())
+}
+
+#[test]
+fn ignores_event_data_logged_by_other_programs() {
+    let transaction = fixtures().into_iter().find(|tx| tx.slot == 120).unwrap();
+    let events = parse_logs(&transaction.logs).unwrap();
+
+    assert_eq!(events.len(), 2);
+    match &events[0] {
+        MasterChefEvent::PoolUpdated(event) => {
+            assert_eq!(event.header.signer, None);
+            assert_eq!((event.last_reward_slot, event.slot), (110, 120));
+            assert_eq!(event.reward_amount, 100);
+        }
+        _ => panic!("expected a pool updated event"),
+    }
+    match &events[1] {
+        MasterChefEvent::Deposit(event) => {
+            assert_eq!(event.header.signer, Some(Pubkey::from_str(USER_TWO).unwrap()));
+            assert_eq!(event.amount, 300);
+            assert_eq!(event.lp_supply, 500);
+        }
+        _ => panic!("expected a deposit event"),
+    }
+}
+
+#[test]
+fn decodes_admin_changes() {
+    let transaction = fixtures().into_iter().find(|tx| tx.slot == 200).unwrap();
+    match parse_logs(&transaction.logs).unwrap().as_slice() {
+        [MasterChefEvent::SetAdmin(event)] => {
+            assert_eq!(event.config.admin, Some(Pubkey::from_str(NEW_ADMIN).unwrap()));
+            assert_eq!(event.config.dev_fund, None);
+        }
+        _ => panic!("expected a set admin event"),
+    }
+
+    let transaction = legacy_fixtures().into_iter().find(|tx| tx.slot == 11).unwrap();
+    match parse_logs(&transaction.logs).unwrap().as_slice() {
+        [MasterChefEvent::LegacySetAdmin(event)] => {
+            assert_eq!(event.config.admin, Some(Pubkey::from_str(NEW_ADMIN).unwrap()));
+        }
+        _ => panic!("expected a legacy set admin event"),
+    }
+}
+
+#[test]
+fn rebuilds_user_histories() {
+    let indexer = replay();
+
+    let user_one = UserKey { pool: pool(), user: Pubkey::from_str(USER_ONE).unwrap(), position: None };
+    let actions: Vec<_> = indexer
+        .user_history(&user_one)
+        .iter()
+        .map(|action| (action.slot, action.kind, action.amount, action.balance))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            (110, ActionKind::Deposit, 200, 200),
+            (130, ActionKind::Withdraw, 100, 100),
+            (140, ActionKind::Claim, 209, 100),
+            (180, ActionKind::TransferIn, 50, 150),
+        ]
+    );
+
+    let user_two = UserKey { pool: pool(), user: Pubkey::from_str(USER_TWO).unwrap(), position: None };
+    let actions: Vec<_> = indexer
+        .user_history(&user_two)
+        .iter()
+        .map(|action| (action.slot, action.kind, action.amount, action.balance))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            (120, ActionKind::Deposit, 300, 300),
+            (150, ActionKind::RequestWithdraw, 100, 200),
+            (170, ActionKind::CompleteWithdraw, 100, 200),
+            (180, ActionKind::TransferOut, 50, 150),
+        ]
+    );
+}
+
+#[test]
+fn tracks_settled_and_accrued_rewards() {
+    let indexer = replay();
+    // the fixtures scale rewards by 2^48
+    let scale = 1u128 << 48;
+
+    let user_one = UserKey { pool: pool(), user: Pubkey::from_str(USER_ONE).unwrap(), position: None };
+    let rewards: Vec<_> = indexer
+        .user_history(&user_one)
+        .iter()
+        .map(|action| (action.slot, action.settled_reward, action.accrued_reward))
+        .collect();
+    assert_eq!(
+        rewards,
+        vec![
+            (110, Some(0), Some(0)),
+            (130, Some(160 * scale - 160), Some(160 * scale - 160)),
+            (140, Some(50 * scale), Some(scale - 160)),
+            (180, Some(150 * scale), Some(151 * scale - 160)),
+        ]
+    );
+
+    let user_two = UserKey { pool: pool(), user: Pubkey::from_str(USER_TWO).unwrap(), position: None };
+    let rewards: Vec<_> = indexer
+        .user_history(&user_two)
+        .iter()
+        .map(|action| (action.slot, action.settled_reward, action.accrued_reward))
+        .collect();
+    let requested = 390 * scale - 240;
+    assert_eq!(
+        rewards,
+        vec![
+            (120, Some(0), Some(0)),
+            (150, Some(requested), Some(requested)),
+            (170, Some(0), Some(requested)),
+            (180, Some(200 * scale), Some(requested + 200 * scale)),
+        ]
+    );
+}
+
+#[test]
+fn keeps_positions_apart_from_wallet_stakes() {
+    let indexer = replay();
+
+    let position = UserKey {
+        pool: pool(),
+        user: Pubkey::from_str(USER_ONE).unwrap(),
+        position: Some(Pubkey::from_str(POSITION_MINT).unwrap()),
+    };
+    let actions: Vec<_> = indexer
+        .user_history(&position)
+        .iter()
+        .map(|action| (action.slot, action.kind, action.amount, action.balance))
+        .collect();
+    assert_eq!(actions, vec![(190, ActionKind::Deposit, 40, 40)]);
+
+    let user_one = UserKey { pool: pool(), user: Pubkey::from_str(USER_ONE).unwrap(), position: None };
+    assert_eq!(indexer.user_history(&user_one).last().unwrap().balance, 150);
+}
+
+#[test]
+fn rebuilds_pool_tvl_series() {
+    let indexer = replay();
+
+    let tvl: Vec<_> = indexer.tvl_series(&pool()).iter().map(|point| (point.slot, point.tvl)).collect();
+    assert_eq!(tvl, vec![(101, 0), (110, 200), (120, 500), (130, 400), (150, 300), (190, 340)]);
+}
+
+#[test]
+fn replays_legacy_events() {
+    let indexer = replay_all(legacy_fixtures());
+    assert!(indexer.skipped().is_empty());
+
+    let user_one = UserKey { pool: pool(), user: Pubkey::from_str(USER_ONE).unwrap(), position: None };
+    let actions: Vec<_> = indexer
+        .user_history(&user_one)
+        .iter()
+        .map(|action| (action.slot, action.kind, action.amount, action.balance, action.accrued_reward))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            (20, ActionKind::Deposit, 200, 200, None),
+            (40, ActionKind::Withdraw, 50, 150, None),
+            (50, ActionKind::Claim, 25, 150, None),
+        ]
+    );
+
+    // the deposit after the upgrade reports the balance itself
+    let user_two = UserKey { pool: pool(), user: Pubkey::from_str(USER_TWO).unwrap(), position: None };
+    let actions: Vec<_> = indexer
+        .user_history(&user_two)
+        .iter()
+        .map(|action| (action.slot, action.kind, action.amount, action.balance, action.settled_reward.is_some()))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![(30, ActionKind::Deposit, 300, 300, false), (60, ActionKind::Deposit, 100, 400, true)]
+    );
+
+    let tvl: Vec<_> = indexer.tvl_series(&pool()).iter().map(|point| (point.slot, point.tvl)).collect();
+    assert_eq!(tvl, vec![(12, 0), (20, 200), (30, 500), (40, 450), (60, 550)]);
+}
+
+#[test]
+fn skips_transactions_that_fail_to_decode() {
+    let mut transactions = fixtures();
+    let deposit = transactions.iter_mut().find(|tx| tx.slot == 110).unwrap();
+    let line = deposit.logs.iter_mut().rfind(|line| line.starts_with("Program data: ")).unwrap();
+    let mut data = STANDARD.decode(line.trim_start_matches("Program data: ")).unwrap();
+    data.truncate(data.len() - 8);
+    *line = format!("Program data: {}", STANDARD.encode(data));
+
+    let indexer = replay_all(transactions);
+    let skipped: Vec<_> = indexer.skipped().iter().map(|skipped| skipped.slot).collect();
+    assert_eq!(skipped, vec![110]);
+
+    // the rest of the transactions are still indexed
+    let user_two = UserKey { pool: pool(), user: Pubkey::from_str(USER_TWO).unwrap(), position: None };
+    assert_eq!(indexer.user_history(&user_two).len(), 4);
+    let user_one = UserKey { pool: pool(), user: Pubkey::from_str(USER_ONE).unwrap(), position: None };
+    assert_eq!(indexer.user_history(&user_one)[0].slot, 130);
+}
+
+#[test]
+fn exports_csv_and_json() {
+    let indexer = replay();
+
+    let mut users_csv = Vec::new();
+    export::write_user_histories_csv(&indexer, &mut users_csv).unwrap();
+    let users_csv = String::from_utf8(users_csv).unwrap();
+    let mut lines = users_csv.lines();
+    assert_eq!(lines.next(), Some("master_chef,lp_token,user,position,slot,signature,kind,amount,balance,settled_reward,accrued_reward"));
+    assert_eq!(lines.count(), 9);
+
+    let mut tvl_csv = Vec::new();
+    export::write_tvl_csv(&indexer, &mut tvl_csv).unwrap();
+    assert_eq!(String::from_utf8(tvl_csv).unwrap().lines().count(), 7);
+
+    let mut json = Vec::new();
+    export::write_json(&indexer, &mut json).unwrap();
+    let document: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(document["users"].as_array().unwrap().len(), 3);
+    assert_eq!(document["users"][0]["position"], serde_json::Value::Null);
+    assert_eq!(document["pools"][0]["lp_token"], LP_TOKEN);
+    assert_eq!(document["pools"][0]["tvl"][3]["tvl"], 400);
+}
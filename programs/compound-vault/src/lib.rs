@@ -0,0 +1,688 @@
+#![allow(clippy::result_large_err)]
+
+//! Auto-compounding vault on top of master-chef.
+//!
+//! Depositors hand their LP tokens to the vault and receive vault shares. The vault PDA
+//! stakes everything it holds in a master-chef pool, and the vault's keeper calls `compound`
+//! to claim the pool rewards, swap them into LP tokens through the vault's swap program and
+//! stake those too, so every share is worth a growing amount of LP. Deposits and withdrawals
+//! must follow a `compound` in the same transaction, so shares are never priced without the
+//! rewards the vault has earned. Vaults are created by the master-chef admin, who also picks
+//! the swap program and the keeper. The pool must pay its rewards from its vault and have no
+//! gate, receipt mint or unbonding period.
+
+pub mod swap;
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked},
+    Discriminator,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Approve, Burn, Mint, MintTo, Revoke, Token, TokenAccount, Transfer},
+};
+use master_chef::{cpi::accounts as master_chef_accounts, program::MasterChef, state::MasterChef as MasterChefState};
+
+use swap::SwapAccounts;
+
+declare_id!("3u8QeCaejvZUahzkUQrQ56CxpG4jvi4jSPPWD1J6vER3");
+
+pub const VAULT_SEED: &str = "vault";
+
+pub const SHARE_MINT_SEED: &str = "share_mint";
+
+pub const SWAP_AUTHORITY_SEED: &str = "swap_authority";
+
+macro_rules! vault_seeds {
+    ($vault: expr) => {
+        &[
+            VAULT_SEED.as_bytes(),
+            $vault.master_chef.as_ref(),
+            $vault.lp_token.as_ref(),
+            &[$vault.bump],
+        ]
+    };
+}
+
+#[program]
+pub mod compound_vault {
+    use super::*;
+
+    /// Creates the vault of a pool. Only the master-chef admin can, since the swap program
+    /// picked here is trusted with the pool rewards for good.
+    pub fn initialize(ctx: Context<Initialize>, keeper: Pubkey) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.master_chef = ctx.accounts.master_chef.key();
+        vault.lp_token = ctx.accounts.lp_mint.key();
+        vault.reward_mint = ctx.accounts.reward_mint.key();
+        vault.share_mint = ctx.accounts.share_mint.key();
+        vault.swap_program = ctx.accounts.swap_program.key();
+        vault.keeper = keeper;
+        vault.bump = *ctx.bumps.get("vault").unwrap();
+        Ok(())
+    }
+
+    /// Replaces the keeper allowed to compound, signed by the master-chef admin.
+    pub fn set_keeper(ctx: Context<SetKeeper>, keeper: Pubkey) -> Result<()> {
+        ctx.accounts.vault.keeper = keeper;
+        Ok(())
+    }
+
+    /// Stakes `amount` LP tokens and mints shares at the current LP per share. Once the vault
+    /// has shares, the keeper has to compound earlier in the same transaction.
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        if ctx.accounts.share_mint.supply > 0 {
+            require_compounded(&ctx.accounts.instructions, &ctx.accounts.vault.key())?;
+        }
+        let shares = ctx.accounts.vault.shares_for(amount, ctx.accounts.share_mint.supply)?;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_lp_token_account.to_account_info(),
+                    to: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+        ctx.accounts.stake_accounts().deposit(&ctx.accounts.vault, amount)?;
+
+        let vault = &ctx.accounts.vault;
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    to: ctx.accounts.user_share_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds!(vault)],
+            ),
+            shares,
+        )?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_staked = vault.total_staked.checked_add(amount).ok_or(VaultError::MathError)?;
+        Ok(())
+    }
+
+    /// Burns `shares` and pays out the LP tokens they are worth, after the keeper compounded
+    /// earlier in the same transaction.
+    pub fn withdraw(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+        require_compounded(&ctx.accounts.instructions, &ctx.accounts.vault.key())?;
+        let amount = ctx.accounts.vault.amount_for(shares, ctx.accounts.share_mint.supply)?;
+
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.share_mint.to_account_info(),
+                    from: ctx.accounts.user_share_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+
+        let vault = &ctx.accounts.vault;
+        master_chef::cpi::withdraw(
+            CpiContext::new_with_signer(
+                ctx.accounts.master_chef_program.to_account_info(),
+                master_chef_accounts::Withdraw {
+                    user: vault.to_account_info(),
+                    master_chef: ctx.accounts.master_chef.to_account_info(),
+                    user_lp_token_account: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    user_info: ctx.accounts.vault_user_info.to_account_info(),
+                    lp_token_vault: ctx.accounts.lp_token_vault.to_account_info(),
+                    lp_token_vault_authority: ctx.accounts.lp_token_vault_authority.to_account_info(),
+                    receipt_mint: None,
+                    user_receipt_token_account: None,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[vault_seeds!(vault)],
+            ),
+            vault.lp_token,
+            amount,
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    to: ctx.accounts.user_lp_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds!(vault)],
+            ),
+            amount,
+        )?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_staked -= amount;
+        Ok(())
+    }
+
+    /// Claims the pool rewards, swaps them into LP tokens and stakes those. Only the keeper
+    /// can call it, since it picks `min_lp_out`. The swap program specific accounts are
+    /// passed as remaining accounts.
+    pub fn compound<'info>(ctx: Context<'_, '_, '_, 'info, Compound<'info>>, min_lp_out: u64) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        master_chef::cpi::claim_reward(
+            CpiContext::new_with_signer(
+                ctx.accounts.master_chef_program.to_account_info(),
+                master_chef_accounts::ClaimReward {
+                    master_chef: ctx.accounts.master_chef.to_account_info(),
+                    user: vault.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    reward_mint: ctx.accounts.reward_mint.to_account_info(),
+                    user_reward_token_account: ctx.accounts.vault_reward_token_account.to_account_info(),
+                    user_info: ctx.accounts.vault_user_info.to_account_info(),
+                    referrer_info: None,
+                    reward_token_vault: ctx.accounts.reward_token_vault.to_account_info(),
+                    reward_token_vault_authority: ctx.accounts.reward_token_vault_authority.to_account_info(),
+                    mint_authority: None,
+                    dev_fund_token_account: None,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[vault_seeds!(vault)],
+            ),
+            vault.lp_token,
+        )?;
+
+        ctx.accounts.vault_reward_token_account.reload()?;
+        let reward_amount = ctx.accounts.vault_reward_token_account.amount;
+        if reward_amount == 0 {
+            return Ok(());
+        }
+
+        // the swap program only gets a delegation over the claimed rewards, never the vault
+        // signature, which also owns the vault's stake and LP tokens
+        token::approve(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.vault_reward_token_account.to_account_info(),
+                    delegate: ctx.accounts.swap_authority.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds!(vault)],
+            ),
+            reward_amount,
+        )?;
+        let lp_before = ctx.accounts.vault_lp_token_account.amount;
+        let vault_key = vault.key();
+        swap::swap(
+            SwapAccounts {
+                swap_program: ctx.accounts.swap_program.to_account_info(),
+                authority: ctx.accounts.swap_authority.to_account_info(),
+                source: ctx.accounts.vault_reward_token_account.to_account_info(),
+                destination: ctx.accounts.vault_lp_token_account.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            ctx.remaining_accounts,
+            reward_amount,
+            min_lp_out,
+            &[&[SWAP_AUTHORITY_SEED.as_bytes(), vault_key.as_ref(), &[*ctx.bumps.get("swap_authority").unwrap()]]],
+        )?;
+        token::revoke(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Revoke {
+                source: ctx.accounts.vault_reward_token_account.to_account_info(),
+                authority: vault.to_account_info(),
+            },
+            &[vault_seeds!(vault)],
+        ))?;
+        ctx.accounts.vault_lp_token_account.reload()?;
+        let lp_amount = ctx.accounts.vault_lp_token_account.amount.saturating_sub(lp_before);
+        // the swap program is only trusted to move tokens, the slippage bound is enforced here
+        require_gte!(lp_amount, min_lp_out, VaultError::SlippageExceeded);
+
+        ctx.accounts.stake_accounts().deposit(&ctx.accounts.vault, lp_amount)?;
+
+        let vault = &mut ctx.accounts.vault;
+        vault.total_staked = vault.total_staked.checked_add(lp_amount).ok_or(VaultError::MathError)?;
+        emit!(CompoundEvent {
+            vault: vault.key(),
+            reward_amount,
+            lp_amount,
+            total_staked: vault.total_staked,
+            total_shares: ctx.accounts.share_mint.supply,
+        });
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Math error")]
+    MathError,
+
+    #[msg("Amount is too small to be worth a share")]
+    ZeroShares,
+
+    #[msg("Swap returned less LP tokens than the minimum")]
+    SlippageExceeded,
+
+    #[msg("The vault must be compounded earlier in the same transaction")]
+    NotCompounded,
+}
+
+#[event]
+pub struct CompoundEvent {
+    pub vault: Pubkey,
+    pub reward_amount: u64,
+    pub lp_amount: u64,
+    pub total_staked: u64,
+    pub total_shares: u64,
+}
+
+#[account]
+#[derive(Default)]
+pub struct Vault {
+    pub master_chef: Pubkey,
+    pub lp_token: Pubkey,
+    pub reward_mint: Pubkey,
+    pub share_mint: Pubkey,
+    /// Program rewards are swapped through, see `swap::swap`.
+    pub swap_program: Pubkey,
+    /// Only signer allowed to compound.
+    pub keeper: Pubkey,
+    /// LP tokens staked in master-chef by the vault.
+    pub total_staked: u64,
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const LEN: usize = 8 + 32 * 6 + 8 + 1;
+
+    /// Shares minted for `amount` LP tokens, 1:1 while the vault is empty.
+    pub fn shares_for(&self, amount: u64, total_shares: u64) -> Result<u64> {
+        let shares = if total_shares == 0 || self.total_staked == 0 {
+            amount
+        } else {
+            u64::try_from(u128::from(amount) * u128::from(total_shares) / u128::from(self.total_staked))
+                .map_err(|_| VaultError::MathError)?
+        };
+        require_gt!(shares, 0, VaultError::ZeroShares);
+        Ok(shares)
+    }
+
+    /// LP tokens paid out for burning `shares`, rounded down.
+    pub fn amount_for(&self, shares: u64, total_shares: u64) -> Result<u64> {
+        require_gt!(total_shares, 0, VaultError::ZeroShares);
+        let amount = u64::try_from(u128::from(shares) * u128::from(self.total_staked) / u128::from(total_shares))
+            .map_err(|_| VaultError::MathError)?;
+        require_gt!(amount, 0, VaultError::ZeroShares);
+        Ok(amount)
+    }
+}
+
+/// Fails unless an earlier instruction of the transaction compounds `vault`. Only the keeper
+/// can sign one and the whole transaction fails with it, so the rewards pending at this
+/// point have been staked.
+fn require_compounded(instructions: &AccountInfo, vault: &Pubkey) -> Result<()> {
+    let current = load_current_index_checked(instructions)?;
+    for index in 0..current {
+        let instruction = load_instruction_at_checked(usize::from(index), instructions)?;
+        // `vault` is the third account of `Compound`
+        if instruction.program_id == crate::ID
+            && instruction.data.starts_with(&instruction::Compound::DISCRIMINATOR)
+            && instruction.accounts.get(2).map(|meta| meta.pubkey) == Some(*vault)
+        {
+            return Ok(());
+        }
+    }
+    err!(VaultError::NotCompounded)
+}
+
+/// Accounts staking the LP tokens held by the vault into its master-chef pool.
+pub struct StakeAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub master_chef: AccountInfo<'info>,
+    pub vault_lp_token_account: AccountInfo<'info>,
+    pub vault_user_info: AccountInfo<'info>,
+    pub lp_token_vault: AccountInfo<'info>,
+    pub master_chef_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> StakeAccounts<'info> {
+    pub fn deposit(self, vault: &Account<'info, Vault>, amount: u64) -> Result<()> {
+        master_chef::cpi::deposit(
+            CpiContext::new_with_signer(
+                self.master_chef_program,
+                master_chef_accounts::Deposit {
+                    user: vault.to_account_info(),
+                    payer: self.payer,
+                    master_chef: self.master_chef,
+                    user_lp_token_account: self.vault_lp_token_account,
+                    user_info: self.vault_user_info,
+                    allowlist_entry: None,
+                    referrer_info: None,
+                    lp_token_vault: self.lp_token_vault,
+                    receipt_mint: None,
+                    user_receipt_token_account: None,
+                    mint_authority: None,
+                    token_program: self.token_program,
+                    system_program: self.system_program,
+                },
+                &[vault_seeds!(vault)],
+            ),
+            vault.lp_token,
+            amount,
+            vec![],
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub admin: Signer<'info>,
+
+    #[account(has_one = admin)]
+    pub master_chef: AccountLoader<'info, MasterChefState>,
+
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Reward token of the pool, validated by master-chef on every claim
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Any program implementing the swap interface of `swap::swap`
+    #[account(executable)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Vault::LEN,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+            lp_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = lp_mint.decimals,
+        mint::authority = vault,
+        seeds = [
+            SHARE_MINT_SEED.as_bytes(),
+            vault.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_reward_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetKeeper<'info> {
+
+    pub admin: Signer<'info>,
+
+    #[account(has_one = admin)]
+    pub master_chef: AccountLoader<'info, MasterChefState>,
+
+    #[account(mut, has_one = master_chef)]
+    pub vault: Box<Account<'info, Vault>>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            vault.master_chef.as_ref(),
+            vault.lp_token.as_ref(),
+        ],
+        bump = vault.bump,
+        has_one = master_chef,
+        has_one = share_mint,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(mut)]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = share_mint)]
+    pub user_share_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.lp_token,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub master_chef: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub vault_user_info: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub lp_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub master_chef_program: Program<'info, MasterChef>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    fn stake_accounts(&self) -> StakeAccounts<'info> {
+        StakeAccounts {
+            payer: self.user.to_account_info(),
+            master_chef: self.master_chef.to_account_info(),
+            vault_lp_token_account: self.vault_lp_token_account.to_account_info(),
+            vault_user_info: self.vault_user_info.to_account_info(),
+            lp_token_vault: self.lp_token_vault.to_account_info(),
+            master_chef_program: self.master_chef_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            vault.master_chef.as_ref(),
+            vault.lp_token.as_ref(),
+        ],
+        bump = vault.bump,
+        has_one = master_chef,
+        has_one = share_mint,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(mut)]
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, token::mint = share_mint)]
+    pub user_share_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.lp_token,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub master_chef: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub vault_user_info: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub lp_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    pub lp_token_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, checked by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub master_chef_program: Program<'info, MasterChef>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Compound<'info> {
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            vault.master_chef.as_ref(),
+            vault.lp_token.as_ref(),
+        ],
+        bump = vault.bump,
+        has_one = master_chef,
+        has_one = share_mint,
+        has_one = reward_mint,
+        has_one = swap_program,
+        has_one = keeper,
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    pub share_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.lp_token,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.reward_mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_reward_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub master_chef: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub vault_user_info: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub lp_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub reward_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    pub reward_token_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the vault
+    pub swap_program: UncheckedAccount<'info>,
+
+    /// CHECK: Seed constraint check, delegate of the claimed rewards during the swap
+    #[account(
+        seeds = [
+            SWAP_AUTHORITY_SEED.as_bytes(),
+            vault.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub swap_authority: UncheckedAccount<'info>,
+
+    pub master_chef_program: Program<'info, MasterChef>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Compound<'info> {
+    fn stake_accounts(&self) -> StakeAccounts<'info> {
+        StakeAccounts {
+            payer: self.payer.to_account_info(),
+            master_chef: self.master_chef.to_account_info(),
+            vault_lp_token_account: self.vault_lp_token_account.to_account_info(),
+            vault_user_info: self.vault_user_info.to_account_info(),
+            lp_token_vault: self.lp_token_vault.to_account_info(),
+            master_chef_program: self.master_chef_program.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
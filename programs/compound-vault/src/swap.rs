@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+/// Accounts every swap program plugged into a vault receives first, in this order.
+pub struct SwapAccounts<'info> {
+    pub swap_program: AccountInfo<'info>,
+    /// Delegate of `source` for the amount swapped, signs the swap.
+    pub authority: AccountInfo<'info>,
+    pub source: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Calls `swap(amount_in: u64, min_amount_out: u64)` on the swap program. Any Anchor program
+/// exposing an instruction with that name and arguments, taking the `SwapAccounts` first and
+/// its own accounts after them, can be plugged into a vault. `remaining_accounts` are passed
+/// through as those program specific accounts.
+pub fn swap<'info>(
+    accounts: SwapAccounts<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    amount_in: u64,
+    min_amount_out: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = hash(b"global:swap").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&min_amount_out.to_le_bytes());
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(accounts.authority.key(), true),
+        AccountMeta::new(accounts.source.key(), false),
+        AccountMeta::new(accounts.destination.key(), false),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ];
+    metas.extend(remaining_accounts.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_signer: account.is_signer,
+        is_writable: account.is_writable,
+    }));
+
+    let mut infos = vec![
+        accounts.authority,
+        accounts.source,
+        accounts.destination,
+        accounts.token_program,
+    ];
+    infos.extend_from_slice(remaining_accounts);
+    infos.push(accounts.swap_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: accounts.swap_program.key(),
+            accounts: metas,
+            data,
+        },
+        &infos,
+        signer_seeds,
+    )?;
+    Ok(())
+}
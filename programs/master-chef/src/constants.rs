@@ -4,4 +4,26 @@ pub const LP_TOKEN_VAULT_AUTHORITY_SEED: &str = "lp_token_vault_auth";
 
 pub const REWARD_TOKEN_VAULT_SEED: &str = "reward_token_vault";
 
-pub const REWARD_TOKEN_VAULT_AUTHORITY_SEED: &str = "reward_token_vault_auth";
\ No newline at end of file
+pub const REWARD_TOKEN_VAULT_AUTHORITY_SEED: &str = "reward_token_vault_auth";
+
+pub const ALLOWLIST_SEED: &str = "allowlist";
+
+pub const REFERRER_SEED: &str = "referrer";
+
+pub const MINT_AUTHORITY_SEED: &str = "mint_authority";
+
+pub const POSITION_SEED: &str = "position";
+
+pub const RECEIPT_MINT_SEED: &str = "receipt_mint";
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Slots after `close_slot` a closed pool keeps rewards claimable, about 90 days of 400ms
+/// slots. `finalize_close_pool` forfeits whatever is still owed past it and frees the slot.
+pub const CLAIM_DEADLINE_SLOTS: u64 = 19_440_000;
+
+pub const ACCOUNT_VERSION: u8 = 2;
+
+/// Scale of `acc_reward_per_share` and of the rewards accrued by users, which are kept in
+/// 1e-18 reward token base units. See `PoolInfo::accrue_reward` for the bounds.
+pub const ACC_REWARD_PRECISION: u128 = 1_000_000_000_000_000_000;
\ No newline at end of file
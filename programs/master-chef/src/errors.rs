@@ -20,4 +20,133 @@ pub enum MasterChefError {
 
     #[msg("LP token not enough")]
     LPTokenNotEnough,
+
+    #[msg("Signer is not the admin")]
+    Unauthorized,
+
+    #[msg("Account data does not match any known version")]
+    UnknownAccountVersion,
+
+    #[msg("Account is already at the current version")]
+    AlreadyMigrated,
+
+    #[msg("User still has staked LP tokens or unclaimed rewards")]
+    UserInfoNotEmpty,
+
+    #[msg("Pool is closing")]
+    PoolClosing,
+
+    #[msg("Pool is not closing")]
+    PoolNotClosing,
+
+    #[msg("Pool grace period is not over")]
+    GracePeriodNotOver,
+
+    #[msg("Pool still has staked LP tokens")]
+    PoolNotEmpty,
+
+    #[msg("LP token vault holds unstaked tokens, an LP treasury must receive them")]
+    LpTreasuryMissing,
+
+    #[msg("Amount exceeds the rewards not owed to stakers")]
+    InsufficientExcessRewards,
+
+    #[msg("Invalid pool vault authority")]
+    InvalidVaultAuthority,
+
+    #[msg("Pool vaults cannot be rescued")]
+    CannotRescuePoolVault,
+
+    #[msg("Deposit exceeds the pool cap")]
+    PoolDepositCapExceeded,
+
+    #[msg("Deposit exceeds the per user cap")]
+    UserDepositCapExceeded,
+
+    #[msg("User is not on the pool allowlist")]
+    NotAllowlisted,
+
+    #[msg("Invalid Merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Pool requires request_withdraw and complete_withdraw")]
+    UnbondingRequired,
+
+    #[msg("Cooldown is not over")]
+    CooldownNotOver,
+
+    #[msg("No withdrawal is cooling down")]
+    NothingCooling,
+
+    #[msg("Referral rate exceeds 100%")]
+    InvalidReferralRate,
+
+    #[msg("Referrer account is required")]
+    ReferrerAccountMissing,
+
+    #[msg("Dev fund rate exceeds 100%")]
+    InvalidDevFundRate,
+
+    #[msg("Mint authority and dev fund accounts are required in mint mode")]
+    MintAccountsMissing,
+
+    #[msg("Minting would exceed the max reward supply")]
+    MaxRewardSupplyExceeded,
+
+    #[msg("Too many emission segments")]
+    TooManySegments,
+
+    #[msg("Emission segments must have increasing start slots")]
+    UnsortedSegments,
+
+    #[msg("Bonus multiplier must be at least 1")]
+    InvalidBonusMultiplier,
+
+    #[msg("Signer does not hold the position NFT")]
+    NotPositionOwner,
+
+    #[msg("Positions cannot be staked into pools with this setting")]
+    UnsupportedPositionPool,
+
+    #[msg("Cannot transfer a position to its own owner")]
+    CannotTransferToSelf,
+
+    #[msg("Receipt mint can only be created while the pool is empty")]
+    ReceiptMintRequiresEmptyPool,
+
+    #[msg("Receipt mint and token account are required by this pool")]
+    ReceiptAccountsMissing,
+
+    #[msg("Receipt mint does not match the pool")]
+    InvalidReceiptMint,
+
+    #[msg("Too many pools in one batch")]
+    BatchTooLarge,
+
+    #[msg("Batch accounts do not match the pools")]
+    BatchAccountsMismatch,
+
+    #[msg("Pool needs accounts a batch cannot pass, use the single pool instruction")]
+    BatchUnsupportedPool,
+
+    #[msg("Vault does not match the pool")]
+    InvalidPoolVault,
+
+    #[msg("Reward mint does not match the pool")]
+    InvalidRewardMint,
+
+    #[msg("Account must be migrated to the current version first")]
+    AccountNotMigrated,
+
+    #[msg("Pool has no whole token of reward dust to sweep")]
+    NoRewardDust,
+
+    #[msg("LP token vault holds less than the staked and cooling supply")]
+    LpVaultShortfall,
+
+    #[msg("Reward vault holds less than the rewards owed")]
+    RewardVaultShortfall,
+
+    #[msg("Rewards owed do not cover the pool's remainder and dust")]
+    RewardAccountingMismatch,
 }
\ No newline at end of file
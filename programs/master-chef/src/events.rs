@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use solana_program::pubkey::Pubkey;
 
-use crate::state::MasterChefConfig;
+use crate::state::{EmissionSegment, MasterChefConfig, PoolConfig, PoolVaultType};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct EventHeader {
@@ -14,6 +14,22 @@ pub struct MasterChefInitializeEvent {
     pub header: EventHeader
 }
 
+#[event]
+pub struct MasterChefMigratedEvent {
+    pub header: EventHeader,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct UserInfoMigratedEvent {
+    pub header: EventHeader,
+    pub user: Pubkey,
+    pub lp_token: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
 #[event]
 pub struct SetAdminEvent {
     pub header: EventHeader,
@@ -37,23 +53,266 @@ pub struct UpadteRewardPerSlotEvent {
     pub new_reward_per_slot: u64,
 }
 
+#[event]
+pub struct PoolUpdatedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub slot: u64,
+    pub last_reward_slot: u64,
+    pub reward_amount: u64,
+    pub lp_supply: u64,
+    pub acc_reward_per_share: u128,
+}
+
+/// LP tokens staked. Staking first settles what the stake earned so far, `settled_reward`
+/// (scaled by `ACC_REWARD_PRECISION`), into `accrued_reward`. `user_amount`, `lp_supply`
+/// and `acc_reward_per_share` already include the deposit. `position` is the position
+/// mint when staking into a position, the signer is its holder.
 #[event]
 pub struct DepositEvent {
     pub header: EventHeader,
     pub lp_token: Pubkey,
     pub amount: u64,
+    pub user_amount: u64,
+    pub settled_reward: u128,
+    pub accrued_reward: u128,
+    pub lp_supply: u64,
+    pub acc_reward_per_share: u128,
+    pub slot: u64,
+    pub position: Option<Pubkey>,
 }
 
+/// LP tokens unstaked and paid back right away, from pools without unbonding or closing ones. The reward
+/// earned up to the withdrawal, `settled_reward`, stays claimable in `accrued_reward`;
+/// `user_amount` and `lp_supply` are what is left staked. `position` is the position mint
+/// when unstaking from a position, the signer is its holder.
 #[event]
 pub struct WithdrawEvent {
     pub header: EventHeader,
     pub lp_token: Pubkey,
     pub amount: u64,
+    pub user_amount: u64,
+    pub settled_reward: u128,
+    pub accrued_reward: u128,
+    pub lp_supply: u64,
+    pub acc_reward_per_share: u128,
+    pub slot: u64,
+    pub position: Option<Pubkey>,
 }
 
+/// Whole reward tokens paid out of `accrued_reward`. The claim first settles what the stake
+/// earned since its last update, `settled_reward`, and `accrued_reward` is the fraction of a
+/// token left for later, both scaled by `ACC_REWARD_PRECISION`. `position` is the position
+/// mint when claiming for a position, the signer is its holder.
 #[event]
 pub struct ClaimRewardEvent {
     pub header: EventHeader,
     pub lp_token: Pubkey,
     pub amount: u64,
+    pub user_amount: u64,
+    pub settled_reward: u128,
+    pub accrued_reward: u128,
+    pub lp_supply: u64,
+    pub acc_reward_per_share: u128,
+    pub slot: u64,
+    pub position: Option<Pubkey>,
+}
+
+#[event]
+pub struct UserInfoClosedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+}
+
+#[event]
+pub struct PoolClosingEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub close_slot: u64,
+}
+
+/// `reward_owed` and `referral_owed` stay claimable, the slot is only freed once both are zero.
+/// `forfeited_reward` is what was still owed past the claim deadline, it is part of
+/// `returned_reward`. `returned_lp` is what was sent to the LP token vault without staking.
+#[event]
+pub struct PoolClosedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub treasury: Pubkey,
+    pub returned_reward: u64,
+    pub forfeited_reward: u64,
+    pub returned_lp: u64,
+    pub reward_owed: u64,
+    pub referral_owed: u64,
+    pub slot_freed: bool,
+}
+
+#[event]
+pub struct WithdrawExcessRewardsEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub reward_owed: u64,
+}
+
+#[event]
+pub struct RescueTokensEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub vault_type: PoolVaultType,
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ConfigurePoolEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub config: PoolConfig,
+}
+
+#[event]
+pub struct AllowlistUpdatedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub user: Pubkey,
+    pub allowed: bool,
+}
+
+/// Stake moved into cooldown in an unbonding pool. `amount` stops earning now but only
+/// leaves the vault with `WithdrawCompletedEvent` after `cooldown_end_slot`; `cooling_amount`
+/// is everything cooling for the user so far. The reward settled by the request is in
+/// `settled_reward` and `accrued_reward`, scaled by `ACC_REWARD_PRECISION`.
+#[event]
+pub struct WithdrawRequestedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+    pub user_amount: u64,
+    pub cooling_amount: u64,
+    pub cooldown_end_slot: u64,
+    pub settled_reward: u128,
+    pub accrued_reward: u128,
+    pub lp_supply: u64,
+    pub acc_reward_per_share: u128,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WithdrawCompletedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+    pub user_amount: u64,
+    pub cooling_supply: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct RegisterReferrerEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+}
+
+#[event]
+pub struct ReferralRewardAccruedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub accrued_reward: u64,
+}
+
+#[event]
+pub struct ClaimReferralRewardEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+    pub total_reward: u64,
+}
+
+/// Rewards minted by a mint mode pool, `supply` is the reward mint supply afterwards.
+#[event]
+pub struct RewardMintedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+    pub dev_fund_amount: u64,
+    pub supply: u64,
+}
+
+#[event]
+pub struct SetEmissionScheduleEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub segments: Vec<EmissionSegment>,
+    pub halving_period: u64,
+}
+
+#[event]
+pub struct PositionOpenedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub position_mint: Pubkey,
+}
+
+#[event]
+pub struct PositionClosedEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub position_mint: Pubkey,
+}
+
+/// Stake moved from the signer to `recipient`. Both sides are settled first, so the
+/// `*_settled_reward` fields are what each had earned on its old amount and `*_accrued_reward`
+/// what each can claim now, scaled by `ACC_REWARD_PRECISION`.
+#[event]
+pub struct TransferPositionEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub user_amount: u64,
+    pub recipient_amount: u64,
+    pub user_settled_reward: u128,
+    pub user_accrued_reward: u128,
+    pub recipient_settled_reward: u128,
+    pub recipient_accrued_reward: u128,
+    pub acc_reward_per_share: u128,
+    pub slot: u64,
+}
+
+#[event]
+pub struct CreateReceiptMintEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub receipt_mint: Pubkey,
+}
+
+#[event]
+pub struct SweepRewardDustEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub amount: u64,
+    pub reward_dust: u128,
+    pub reward_owed: u64,
+}
+
+#[event]
+pub struct AuditEvent {
+    pub header: EventHeader,
+    pub lp_token: Pubkey,
+    pub slot: u64,
+    pub lp_supply: u64,
+    pub cooling_supply: u64,
+    pub lp_token_vault_amount: u64,
+    pub reward_owed: u64,
+    pub reward_token_vault_amount: u64,
+    pub reward_remainder: u128,
+    pub reward_dust: u128,
+    pub referral_owed: u64,
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, events::{AllowlistUpdatedEvent, EventHeader}, AllowlistEntry, MasterChef};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey, user: Pubkey)]
+pub struct AddToAllowlist<'info> {
+
+    #[account(
+        mut,
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = AllowlistEntry::LEN,
+        seeds = [
+            ALLOWLIST_SEED.as_bytes(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+            user.as_ref(),
+        ],
+        bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, lp_token: Pubkey, user: Pubkey) -> Result<()> {
+    ctx.accounts.master_chef.load_mut()?.find_pool(&lp_token)?;
+    ctx.accounts.allowlist_entry.bump = *ctx.bumps.get("allowlist_entry").unwrap();
+
+    emit!(AllowlistUpdatedEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.admin.key()),
+        },
+        lp_token,
+        user,
+        allowed: true,
+    });
+    Ok(())
+}
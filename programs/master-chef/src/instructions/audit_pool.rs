@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{check, constants::*, errors::MasterChefError, events::{AuditEvent, EventHeader}, math_error, MasterChef, PoolVaultType};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct AuditPool<'info> {
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    pub lp_token_vault: Box<Account<'info, TokenAccount>>,
+
+    pub reward_token_vault: Box<Account<'info, TokenAccount>>,
+}
+
+/// Checks the bookkeeping of a pool against its token accounts, after bringing its rewards
+/// up to date:
+/// - the LP token vault holds at least `lp_supply` plus `cooling_supply`, more when LP
+///   tokens were sent to it directly,
+/// - in vault mode, the reward vault holds at least `reward_owed` plus `referral_owed`,
+/// - `reward_owed` covers the pool's `reward_remainder` and `reward_dust`.
+///
+/// Anyone can call it, so monitoring can run it periodically or simulate it.
+pub fn audit_pool(ctx: Context<AuditPool>, lp_token: Pubkey) -> Result<()> {
+    let AuditPool {
+        master_chef: master_chef_loader,
+        lp_token_vault,
+        reward_token_vault,
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    pool.check_vault(PoolVaultType::LPTokenVault, &lp_token_vault.key())?;
+    pool.check_vault(PoolVaultType::RewardTokenVault, &reward_token_vault.key())?;
+    pool.update_pool(master_chef_loader.key())?;
+
+    let staked = pool.lp_supply.checked_add(pool.cooling_supply).ok_or_else(math_error!())?;
+    check!(
+        lp_token_vault.amount >= staked,
+        MasterChefError::LpVaultShortfall,
+        "lp token vault: {}, staked and cooling: {}",
+        lp_token_vault.amount,
+        staked
+    );
+    if !pool.is_mint_mode() {
+        let owed = pool.total_owed()?;
+        check!(
+            reward_token_vault.amount >= owed,
+            MasterChefError::RewardVaultShortfall,
+            "reward token vault: {}, owed to stakers and referrers: {}",
+            reward_token_vault.amount,
+            owed
+        );
+    }
+    let undistributed = pool.reward_remainder.checked_add(pool.reward_dust).ok_or_else(math_error!())?;
+    check!(
+        u128::from(pool.reward_owed) * ACC_REWARD_PRECISION >= undistributed,
+        MasterChefError::RewardAccountingMismatch,
+        "owed: {}, remainder and dust: {}",
+        pool.reward_owed,
+        undistributed
+    );
+
+    emit!(AuditEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: None,
+        },
+        lp_token,
+        slot: pool.last_reward_slot,
+        lp_supply: pool.lp_supply,
+        cooling_supply: pool.cooling_supply,
+        lp_token_vault_amount: lp_token_vault.amount,
+        reward_owed: pool.reward_owed,
+        reward_token_vault_amount: reward_token_vault.amount,
+        reward_remainder: pool.reward_remainder,
+        reward_dust: pool.reward_dust,
+        referral_owed: pool.referral_owed,
+    });
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Transfer};
+
+use crate::{check, errors::MasterChefError, events::{ClaimRewardEvent, EventHeader}, math_error, pool_signer, state::MAX_BATCH_POOLS, utils::load_user_info, MasterChef, PoolVaultType};
+
+/// Accounts passed through `remaining_accounts` for every pool of `claim_all`, in order:
+/// `user_info` (writable), `user_reward_token_account` (writable), `reward_token_vault`
+/// (writable) and `reward_token_vault_authority`.
+pub const CLAIM_ALL_ACCOUNTS: usize = 4;
+
+#[derive(Accounts)]
+pub struct ClaimAll<'info> {
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Claims the rewards of several pools at once. Pools in mint mode and pools where the
+/// user's referrer earns a share need more accounts and are rejected, they are claimed
+/// with `claim_reward`.
+pub fn claim_all<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAll<'info>>, lp_tokens: Vec<Pubkey>) -> Result<()> {
+    let ClaimAll {
+        master_chef: master_chef_loader,
+        user,
+        token_program,
+    } = ctx.accounts;
+
+    check!(
+        lp_tokens.len() <= MAX_BATCH_POOLS,
+        MasterChefError::BatchTooLarge
+    );
+    check!(
+        ctx.remaining_accounts.len() == lp_tokens.len() * CLAIM_ALL_ACCOUNTS,
+        MasterChefError::BatchAccountsMismatch
+    );
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let slot = Clock::get()?.slot;
+    for (lp_token, accounts) in lp_tokens.iter().zip(ctx.remaining_accounts.chunks(CLAIM_ALL_ACCOUNTS)) {
+        let [user_info, user_reward_token_account, reward_token_vault, reward_token_vault_authority] = accounts else {
+            return Err(MasterChefError::BatchAccountsMismatch.into());
+        };
+        let mut user_info = load_user_info(user_info, &user.key(), lp_token, &master_chef_loader.key())?;
+        let pool = master_chef.find_pool(lp_token)?;
+        check!(
+            !(pool.is_mint_mode() || (user_info.has_referrer() && pool.referral_bps > 0)),
+            MasterChefError::BatchUnsupportedPool
+        );
+        pool.check_vault(PoolVaultType::RewardTokenVault, reward_token_vault.key)?;
+
+        pool.update_pool(master_chef_loader.key())?;
+
+        let settled_reward = user_info.settle(pool)?;
+        user_info.sync_reward_per_share(pool);
+        let reward_amount = user_info.take_accrued_reward()?;
+        pool.reward_owed = pool.reward_owed.checked_sub(reward_amount).ok_or_else(math_error!())?;
+        pool.withdraw_sql_transfer(
+            reward_amount,
+            Transfer {
+                from: reward_token_vault.clone(),
+                to: user_reward_token_account.clone(),
+                authority: reward_token_vault_authority.clone(),
+            },
+            token_program.to_account_info(),
+            pool_signer!(PoolVaultType::RewardTokenVault, *lp_token, pool.reward_token_vault_authority_bump, master_chef_loader.key()),
+        )?;
+        user_info.exit(&crate::ID)?;
+
+        emit!(ClaimRewardEvent {
+            header: EventHeader {
+                master_chef: master_chef_loader.key(),
+                signer: Some(user.key())
+            },
+            lp_token: *lp_token,
+            amount: reward_amount,
+            user_amount: user_info.amount,
+            settled_reward,
+            accrued_reward: user_info.accrued_reward,
+            lp_supply: pool.lp_supply,
+            acc_reward_per_share: pool.acc_reward_per_share,
+            slot,
+            position: None,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
+
+use crate::{constants::*, errors::MasterChefError, events::{ClaimRewardEvent, EventHeader}, math_error, utils::RewardPayer, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct ClaimPositionReward<'info> {
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Seed constraint of `position_info` and mint of `position_token_account`
+    pub position_mint: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = position_mint,
+        token::authority = owner,
+        constraint = position_token_account.amount == 1 @ MasterChefError::NotPositionOwner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_info: Account<'info, UserInfo>,
+
+    /// Checked against the pool
+    #[account(mut)]
+    pub reward_mint: Account<'info, Mint>,
+
+    /// CHECK: Token mint/authority are checked at transfer
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_reward_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
+    pub reward_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    #[account(mut)]
+    pub reward_token_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: Seed constraint check, only required by mint mode pools
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+
+    /// Only required by mint mode pools minting a dev fund share
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        constraint = dev_fund_token_account.owner == master_chef.load()?.dev_fund,
+    )]
+    pub dev_fund_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays out a position's pending rewards to the current NFT holder. Positions never carry
+/// a referrer, so no referral share is accrued.
+pub fn claim_position_reward(ctx: Context<ClaimPositionReward>, lp_token: Pubkey) -> Result<()> {
+
+    let mint_authority_bump = ctx.bumps.get("mint_authority").copied();
+    let ClaimPositionReward {
+        master_chef: master_chef_loader,
+        owner,
+        position_info,
+        position_mint,
+        owner_reward_token_account,
+        reward_token_vault,
+        reward_token_vault_authority,
+        reward_mint,
+        mint_authority,
+        dev_fund_token_account,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let (dev_fund_bps, max_reward_supply) = (master_chef.dev_fund_bps, master_chef.max_reward_supply);
+    let pool = master_chef.find_pool(&lp_token)?;
+
+    if position_info.amount == 0 && !position_info.has_claimable_reward() {
+        return  Ok(());
+    }
+
+    pool.update_pool(master_chef_loader.key())?;
+
+    let settled_reward = position_info.settle(pool)?;
+    position_info.sync_reward_per_share(pool);
+    let reward_amount = position_info.take_accrued_reward()?;
+    pool.reward_owed = pool.reward_owed.checked_sub(reward_amount).ok_or_else(math_error!())?;
+    RewardPayer {
+        master_chef: master_chef_loader.key(),
+        lp_token,
+        signer: owner.key(),
+        reward_mint,
+        reward_token_vault: reward_token_vault.to_account_info(),
+        reward_token_vault_authority: reward_token_vault_authority.to_account_info(),
+        mint_authority: mint_authority.as_ref().map(|account| account.to_account_info()),
+        mint_authority_bump,
+        dev_fund_token_account: dev_fund_token_account.as_ref().map(|account| account.to_account_info()),
+        token_program: token_program.to_account_info(),
+        dev_fund_bps,
+        max_reward_supply,
+    }.pay(pool, owner_reward_token_account.to_account_info(), reward_amount)?;
+
+    emit!(ClaimRewardEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(owner.key())
+        },
+        lp_token,
+        amount: reward_amount,
+        user_amount: position_info.amount,
+        settled_reward,
+        accrued_reward: position_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+        position: Some(position_mint.key()),
+    });
+
+    Ok(())
+}
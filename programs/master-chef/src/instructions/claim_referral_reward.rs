@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
+
+use crate::{constants::*, events::{ClaimReferralRewardEvent, EventHeader}, math_error, utils::RewardPayer, MasterChef, ReferrerInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct ClaimReferralReward<'info> {
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// Checked against the pool
+    #[account(mut)]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = referrer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = referrer,
+    )]
+    pub referrer_reward_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            REFERRER_SEED.as_bytes(),
+            referrer.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump = referrer_info.bump,
+    )]
+    pub referrer_info: Account<'info, ReferrerInfo>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
+    pub reward_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    pub reward_token_vault_authority: AccountInfo<'info>,
+
+    /// CHECK: Seed constraint check, only required by mint mode pools
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+
+    /// Only required by mint mode pools minting a dev fund share
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        constraint = dev_fund_token_account.owner == master_chef.load()?.dev_fund,
+    )]
+    pub dev_fund_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>, lp_token: Pubkey) -> Result<()> {
+    let mint_authority_bump = ctx.bumps.get("mint_authority").copied();
+    let ClaimReferralReward {
+        master_chef: master_chef_loader,
+        referrer,
+        referrer_reward_token_account,
+        referrer_info,
+        reward_token_vault,
+        reward_token_vault_authority,
+        reward_mint,
+        mint_authority,
+        dev_fund_token_account,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let (dev_fund_bps, max_reward_supply) = (master_chef.dev_fund_bps, master_chef.max_reward_supply);
+    let pool = master_chef.find_pool(&lp_token)?;
+
+    let amount = referrer_info.accrued_reward;
+    if amount == 0 {
+        return Ok(());
+    }
+    referrer_info.accrued_reward = 0;
+    pool.referral_owed = pool.referral_owed.checked_sub(amount).ok_or_else(math_error!())?;
+    RewardPayer {
+        master_chef: master_chef_loader.key(),
+        lp_token,
+        signer: referrer.key(),
+        reward_mint,
+        reward_token_vault: reward_token_vault.to_account_info(),
+        reward_token_vault_authority: reward_token_vault_authority.to_account_info(),
+        mint_authority: mint_authority.as_ref().map(|account| account.to_account_info()),
+        mint_authority_bump,
+        dev_fund_token_account: dev_fund_token_account.as_ref().map(|account| account.to_account_info()),
+        token_program: token_program.to_account_info(),
+        dev_fund_bps,
+        max_reward_supply,
+    }.pay(pool, referrer_reward_token_account.to_account_info(), amount)?;
+
+    emit!(ClaimReferralRewardEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(referrer.key()),
+        },
+        lp_token,
+        amount,
+        total_reward: referrer_info.total_reward,
+    });
+    Ok(())
+}
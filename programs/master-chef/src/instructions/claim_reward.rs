@@ -1,8 +1,7 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount, Transfer}};
-use fixed::types::I80F48;
+use anchor_spl::{associated_token::AssociatedToken, token::{Mint, Token, TokenAccount}};
 
-use crate::{constants::*, events::{ClaimRewardEvent, EventHeader}, math_error, pool_signer, MasterChef, PoolVaultType, UserInfo, WrappedI80F48};
+use crate::{constants::*, errors::MasterChefError, events::{ClaimRewardEvent, EventHeader, ReferralRewardAccruedEvent}, math_error, utils::RewardPayer, MasterChef, ReferrerInfo, UserInfo};
 
 #[derive(Accounts)]
 #[instruction(lp_token: Pubkey)]
@@ -11,16 +10,21 @@ pub struct ClaimReward<'info> {
     #[account(mut)]
     pub master_chef: AccountLoader<'info, MasterChef>,
 
-    #[account(mut)]
+    /// Staking wallet, may be a PDA signing through CPI
     pub user: Signer<'info>,
 
+    /// Pays the rent of a new `user_reward_token_account`
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Checked against the pool
     #[account(mut)]
     pub reward_mint: Account<'info, Mint>,
 
     /// CHECK: Token mint/authority are checked at transfer
     #[account(
         init_if_needed,
-        payer = user,
+        payer = payer,
         associated_token::mint = reward_mint,
         associated_token::authority = user,
     )]
@@ -37,34 +41,45 @@ pub struct ClaimReward<'info> {
     )]
     pub user_info: Account<'info, UserInfo>,
 
-    /// CHECK: Seed constraint check
-    #[
-        account(
-            mut,
-            seeds = [
-                REWARD_TOKEN_VAULT_SEED.as_bytes(),
-                lp_token.as_ref(),
-                master_chef.key().as_ref(),
-            ],
-            bump = master_chef.load_mut()?.find_pool(&lp_token)?.reward_token_vault_bump,
-        )
-    ]
+    /// Required when the user has a referrer
+    #[account(
+        mut,
+        seeds = [
+            REFERRER_SEED.as_bytes(),
+            user_info.referrer.as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump = referrer_info.bump,
+    )]
+    pub referrer_info: Option<Account<'info, ReferrerInfo>>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
     pub reward_token_vault: AccountInfo<'info>,
 
-    /// CHECK: Seed constraint check
-    #[
-        account(
-            mut,
-            seeds = [
-                REWARD_TOKEN_VAULT_AUTHORITY_SEED.as_bytes(),
-                lp_token.as_ref(),
-                master_chef.key().as_ref(),
-            ],
-            bump = master_chef.load_mut()?.find_pool(&lp_token)?.reward_token_vault_authority_bump,
-        )
-    ]
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    #[account(mut)]
     pub reward_token_vault_authority: AccountInfo<'info>,
 
+    /// CHECK: Seed constraint check, only required by mint mode pools
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+
+    /// Only required by mint mode pools minting a dev fund share
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        constraint = dev_fund_token_account.owner == master_chef.load()?.dev_fund,
+    )]
+    pub dev_fund_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -72,46 +87,70 @@ pub struct ClaimReward<'info> {
 
 pub fn claim_reward(ctx: Context<ClaimReward>, lp_token: Pubkey) -> Result<()> {
 
+    let mint_authority_bump = ctx.bumps.get("mint_authority").copied();
     let ClaimReward {
         master_chef: master_chef_loader,
         user,
         user_info,
+        referrer_info,
         user_reward_token_account,
         reward_token_vault,
         reward_token_vault_authority,
+        reward_mint,
+        mint_authority,
+        dev_fund_token_account,
         token_program,
         ..
     } = ctx.accounts;
 
     let mut master_chef = master_chef_loader.load_mut()?;
+    let (dev_fund_bps, max_reward_supply) = (master_chef.dev_fund_bps, master_chef.max_reward_supply);
     let pool = master_chef.find_pool(&lp_token)?;
 
-    if user_info.amount == 0 && <WrappedI80F48 as Into<I80F48>>::into(user_info.accrued_reward).is_zero() {
+    if user_info.amount == 0 && !user_info.has_claimable_reward() {
         return  Ok(());
     }
 
-    pool.update_pool()?;
-
-    let pending = I80F48::from_num(user_info.amount)
-        .checked_mul(pool.acc_reward_per_share
-        .into())
-        .ok_or_else(math_error!())?
-        .checked_sub(user_info.reward_debt.into())
-        .ok_or_else(math_error!())?;
-
-    user_info.reward_debt = I80F48::from_num(user_info.amount).checked_mul(pool.acc_reward_per_share.into()).ok_or_else(math_error!())?.into();
-    let reward_amount: u64 = pending.checked_add(user_info.accrued_reward.into()).ok_or_else(math_error!())?.to_num();
-    user_info.accrued_reward = I80F48::ZERO.into();
-    pool.withdraw_sql_transfer(
-        reward_amount, 
-        Transfer {
-            from: reward_token_vault.to_account_info(),
-            to: user_reward_token_account.to_account_info(),
-            authority: reward_token_vault_authority.to_account_info(),
-    }, 
-    token_program.to_account_info(), 
-        pool_signer!(PoolVaultType::RewardTokenVault, lp_token, pool.reward_token_vault_authority_bump, master_chef_loader.key())
-    )?;
+    pool.update_pool(master_chef_loader.key())?;
+
+    let settled_reward = user_info.settle(pool)?;
+    user_info.sync_reward_per_share(pool);
+    let reward_amount = user_info.take_accrued_reward()?;
+    pool.reward_owed = pool.reward_owed.checked_sub(reward_amount).ok_or_else(math_error!())?;
+    RewardPayer {
+        master_chef: master_chef_loader.key(),
+        lp_token,
+        signer: user.key(),
+        reward_mint,
+        reward_token_vault: reward_token_vault.to_account_info(),
+        reward_token_vault_authority: reward_token_vault_authority.to_account_info(),
+        mint_authority: mint_authority.as_ref().map(|account| account.to_account_info()),
+        mint_authority_bump,
+        dev_fund_token_account: dev_fund_token_account.as_ref().map(|account| account.to_account_info()),
+        token_program: token_program.to_account_info(),
+        dev_fund_bps,
+        max_reward_supply,
+    }.pay(pool, user_reward_token_account.to_account_info(), reward_amount)?;
+
+    if user_info.has_referrer() && pool.referral_bps > 0 {
+        let referrer_info = referrer_info.as_mut().ok_or(MasterChefError::ReferrerAccountMissing)?;
+        // referral_bps is capped at BPS_DENOMINATOR so the result always fits back into a u64
+        let referral_reward = (u128::from(reward_amount) * u128::from(pool.referral_bps) / u128::from(BPS_DENOMINATOR)) as u64;
+        referrer_info.accrued_reward = referrer_info.accrued_reward.checked_add(referral_reward).ok_or_else(math_error!())?;
+        referrer_info.total_reward = referrer_info.total_reward.checked_add(referral_reward).ok_or_else(math_error!())?;
+        pool.referral_owed = pool.referral_owed.checked_add(referral_reward).ok_or_else(math_error!())?;
+
+        emit!(ReferralRewardAccruedEvent {
+            header: EventHeader {
+                master_chef: master_chef_loader.key(),
+                signer: Some(user.key())
+            },
+            lp_token,
+            referrer: referrer_info.referrer,
+            amount: referral_reward,
+            accrued_reward: referrer_info.accrued_reward,
+        });
+    }
 
     emit!(ClaimRewardEvent {
         header: EventHeader {
@@ -120,6 +159,13 @@ pub fn claim_reward(ctx: Context<ClaimReward>, lp_token: Pubkey) -> Result<()> {
         },
         lp_token,
         amount: reward_amount,
+        user_amount: user_info.amount,
+        settled_reward,
+        accrued_reward: user_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+        position: None,
     });
 
     Ok(())
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, PoolClosingEvent}, math_error, state::*};
+
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+
+    #[account(
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+}
+
+/// Stops emissions and blocks new deposits. Users keep withdrawing and claiming until
+/// `grace_slots` have passed, after which `finalize_close_pool` can free the pool slot.
+pub fn close_pool(ctx: Context<ClosePool>, lp_token: Pubkey, grace_slots: u64) -> Result<()> {
+    let mut master_chef = ctx.accounts.master_chef.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
+
+    pool.update_pool(ctx.accounts.master_chef.key())?;
+    pool.reward_per_slot = 0;
+    pool.set_emission_schedule(&[], 0)?;
+    pool.close_slot = Clock::get()?.slot.checked_add(grace_slots).ok_or_else(math_error!())?;
+
+    emit!(PoolClosingEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.admin.key()),
+        },
+        lp_token,
+        close_slot: pool.close_slot,
+    });
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, close_account, Burn, CloseAccount, Mint, Token, TokenAccount};
+
+use crate::{check, constants::*, errors::MasterChefError, events::{EventHeader, PositionClosedEvent}, utils::forfeit_reward_dust, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct ClosePosition<'info> {
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(mut)]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = position_mint,
+        token::authority = owner,
+        constraint = position_token_account.amount == 1 @ MasterChefError::NotPositionOwner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_info: Account<'info, UserInfo>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Burns the NFT of an empty position and returns the rent of its accounts to the holder.
+pub fn close_position(ctx: Context<ClosePosition>, lp_token: Pubkey) -> Result<()> {
+    let ClosePosition {
+        owner,
+        master_chef,
+        position_mint,
+        position_token_account,
+        position_info,
+        token_program,
+    } = ctx.accounts;
+
+    check!(
+        position_info.is_empty(),
+        MasterChefError::UserInfoNotEmpty
+    );
+    forfeit_reward_dust(&mut *master_chef.load_mut()?, &lp_token, position_info)?;
+
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: position_mint.to_account_info(),
+                from: position_token_account.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    close_account(CpiContext::new(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: position_token_account.to_account_info(),
+            destination: owner.to_account_info(),
+            authority: owner.to_account_info(),
+        },
+    ))?;
+
+    emit!(PositionClosedEvent {
+        header: EventHeader {
+            master_chef: master_chef.key(),
+            signer: Some(owner.key()),
+        },
+        lp_token,
+        position_mint: position_mint.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, UserInfoClosedEvent}, utils::forfeit_reward_dust, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct CloseUserInfo<'info> {
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            user.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+}
+
+pub fn close_user_info(ctx: Context<CloseUserInfo>, lp_token: Pubkey) -> Result<()> {
+    let CloseUserInfo {
+        user,
+        master_chef: master_chef_loader,
+        user_info,
+    } = ctx.accounts;
+
+    check!(
+        user_info.is_empty(),
+        MasterChefError::UserInfoNotEmpty
+    );
+    forfeit_reward_dust(&mut *master_chef_loader.load_mut()?, &lp_token, user_info)?;
+
+    emit!(UserInfoClosedEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(user.key()),
+        },
+        lp_token,
+    });
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Transfer};
+
+use crate::{check, errors::*, events::{EventHeader, WithdrawCompletedEvent}, pool_signer, MasterChef, PoolVaultType, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct CompleteWithdraw<'info> {
+
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    /// CHECK: Token mint/authority are checked at transfer
+    #[account(mut)]
+    pub user_lp_token_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            user.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
+    pub lp_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    pub lp_token_vault_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Transfers the whole cooling amount out once its cooldown is over. A closing pool
+/// releases cooling tokens immediately.
+pub fn complete_withdraw(ctx: Context<CompleteWithdraw>, lp_token: Pubkey) -> Result<()> {
+    let CompleteWithdraw {
+        user,
+        master_chef: master_chef_loader,
+        user_lp_token_account,
+        user_info,
+        lp_token_vault,
+        lp_token_vault_authority,
+        token_program,
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    pool.check_vault(PoolVaultType::LPTokenVault, &lp_token_vault.key())?;
+
+    let amount = user_info.cooling_amount;
+    check!(
+        amount > 0,
+        MasterChefError::NothingCooling
+    );
+    let slot = Clock::get()?.slot;
+    check!(
+        slot >= user_info.cooldown_end_slot || pool.is_closing(),
+        MasterChefError::CooldownNotOver,
+        "cooldown ends at slot {}",
+        user_info.cooldown_end_slot
+    );
+
+    pool.withdraw_sql_transfer(
+        amount,
+        Transfer {
+            from: lp_token_vault.to_account_info(),
+            to: user_lp_token_account.to_account_info(),
+            authority: lp_token_vault_authority.to_account_info(),
+        },
+        token_program.to_account_info(),
+        pool_signer!(PoolVaultType::LPTokenVault, lp_token, pool.lp_token_vault_authority_bump, master_chef_loader.key())
+    )?;
+    user_info.cooling_amount = 0;
+    user_info.cooldown_end_slot = 0;
+    pool.cooling_supply -= amount;
+
+    emit!(WithdrawCompletedEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(user.key()),
+        },
+        lp_token,
+        amount,
+        user_amount: user_info.amount,
+        cooling_supply: pool.cooling_supply,
+        slot,
+    });
+
+    Ok(())
+}
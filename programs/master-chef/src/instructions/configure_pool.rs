@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::{ConfigurePoolEvent, EventHeader}, state::{MasterChef, PoolConfig}};
+
+#[derive(Accounts)]
+pub struct ConfigurePool<'info> {
+
+    #[account(
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+}
+
+/// Updates the settings of a pool. Rewards up to the current slot are settled first so a new
+/// bonus only applies from now on. Lowering a deposit cap below the current stake does not
+/// force anyone out, it only blocks further deposits.
+pub fn configure_pool(ctx: Context<ConfigurePool>, lp_token: Pubkey, config: PoolConfig) -> Result<()> {
+    let master_chef = &mut ctx.accounts.master_chef.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    pool.update_pool(ctx.accounts.master_chef.key())?;
+    pool.configure(&config)?;
+
+    emit!(ConfigurePoolEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.admin.key()),
+        },
+        lp_token,
+        config,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{check, constants::*, errors::MasterChefError, events::{CreateReceiptMintEvent, EventHeader}, MasterChef};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct CreateReceiptMint<'info> {
+
+    #[account(
+        mut,
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(address = lp_token)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = lp_mint.decimals,
+        mint::authority = mint_authority,
+        seeds = [
+            RECEIPT_MINT_SEED.as_bytes(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: Seed constraint check
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Gives an empty pool a receipt mint. From then on deposits mint receipt tokens 1:1 and
+/// taking LP tokens out of the pool burns them, so the receipt supply always matches the
+/// wallet stakes of the pool. NFT positions are transferable on their own and mint none.
+pub fn create_receipt_mint(ctx: Context<CreateReceiptMint>, lp_token: Pubkey) -> Result<()> {
+    let CreateReceiptMint {
+        admin,
+        master_chef: master_chef_loader,
+        receipt_mint,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        pool.lp_supply == 0 && pool.cooling_supply == 0,
+        MasterChefError::ReceiptMintRequiresEmptyPool
+    );
+    pool.receipt_mint = receipt_mint.key();
+
+    emit!(CreateReceiptMintEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(admin.key()),
+        },
+        lp_token,
+        receipt_mint: receipt_mint.key(),
+    });
+    Ok(())
+}
@@ -1,16 +1,19 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, Transfer};
-use fixed::types::I80F48;
 
-use crate::{constants::*, events::{DepositEvent, EventHeader}, math_error, MasterChef, UserInfo};
+use crate::{check, constants::*, errors::MasterChefError, events::{DepositEvent, EventHeader}, AllowlistEntry, utils::ReceiptAccounts, MasterChef, ReferrerInfo, UserInfo};
 
 #[derive(Accounts)]
 #[instruction(lp_token: Pubkey)]
 pub struct Deposit<'info> {
 
-    #[account(mut)]
+    /// Staking wallet, may be a PDA signing through CPI
     pub user: Signer<'info>,
 
+    /// Pays the rent of a new `user_info`
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(mut)]
     pub master_chef: AccountLoader<'info, MasterChef>,
 
@@ -20,8 +23,8 @@ pub struct Deposit<'info> {
 
     #[account(
         init_if_needed,
-        payer = user,
-        space = 8 + std::mem::size_of::<MasterChef>(),
+        payer = payer,
+        space = UserInfo::LEN,
         seeds = [
             user.key().as_ref(),
             lp_token.as_ref(),
@@ -31,26 +34,60 @@ pub struct Deposit<'info> {
     )]
     pub user_info: Account<'info, UserInfo>,
 
-    /// CHECK: Seed constraint check
-    #[
-        account(
-            mut,
-            seeds = [
-                LP_TOKEN_VAULT_SEED.as_bytes(),
-                lp_token.key().as_ref(),
-                master_chef.key().as_ref(),
-            ],
-            bump = master_chef.load_mut()?.find_pool(&lp_token)?.lp_token_vault_bump,
-        )
-    ]
+    /// Only required by allowlisted pools
+    #[account(
+        seeds = [
+            ALLOWLIST_SEED.as_bytes(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// Referrer to record on the user's first deposit that names one
+    #[account(
+        mut,
+        seeds = [
+            REFERRER_SEED.as_bytes(),
+            referrer_info.referrer.as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump = referrer_info.bump,
+    )]
+    pub referrer_info: Option<Account<'info, ReferrerInfo>>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
     pub lp_token_vault: AccountInfo<'info>,
 
+    /// CHECK: Checked against the pool, only required by pools with a receipt mint
+    #[account(mut)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token mint/authority are checked at mint/burn
+    #[account(mut)]
+    pub user_receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Seed constraint check, only required by pools with a receipt mint
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 
-pub fn deposit(ctx: Context<Deposit>, lp_token: Pubkey, amount: u64) -> Result<()> {
+pub fn deposit(ctx: Context<Deposit>, lp_token: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let mint_authority_bump = ctx.bumps.get("mint_authority").copied();
     let Deposit {
         master_chef: master_chef_loader,
         user,
@@ -58,26 +95,40 @@ pub fn deposit(ctx: Context<Deposit>, lp_token: Pubkey, amount: u64) -> Result<(
         lp_token_vault,
         token_program,
         user_info,
+        allowlist_entry,
+        referrer_info,
+        receipt_mint,
+        user_receipt_token_account,
+        mint_authority,
         ..
     } = ctx.accounts;
 
 
     let mut master_chef = master_chef_loader.load_mut()?;
     let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
+    user_info.check_version()?;
+
+    pool.update_pool(master_chef_loader.key())?;
 
-    pool.update_pool()?;
+    if let Some(referrer_info) = referrer_info {
+        if !user_info.has_referrer() && referrer_info.referrer != user.key() {
+            user_info.referrer = referrer_info.referrer;
+            referrer_info.referee_count += 1;
+        }
+    }
 
+    let mut settled_reward = 0;
     if user_info.amount > 0 {
-        let pending = I80F48::from_num(user_info.amount)
-            .checked_mul(pool.acc_reward_per_share
-            .into())
-            .ok_or_else(math_error!())?
-            .checked_sub(user_info.reward_debt.into())
-            .ok_or_else(math_error!())?;
-        user_info.accrued_reward = pending.checked_add(user_info.accrued_reward.into()).ok_or_else(math_error!())?.into();
+        settled_reward = user_info.settle(pool)?;
     }
 
     if amount > 0 {
+        pool.check_gate(&user.key(), allowlist_entry.is_some(), &proof)?;
+        pool.check_deposit_caps(user_info.amount, amount)?;
         pool.deposit_spl_transfer(
             amount, 
             Transfer {
@@ -89,8 +140,16 @@ pub fn deposit(ctx: Context<Deposit>, lp_token: Pubkey, amount: u64) -> Result<(
         )?;
         user_info.amount += amount;
         pool.lp_supply += amount;
+        ReceiptAccounts {
+            master_chef: master_chef_loader.key(),
+            receipt_mint: receipt_mint.as_ref().map(|account| account.to_account_info()),
+            receipt_token_account: user_receipt_token_account.as_ref().map(|account| account.to_account_info()),
+            mint_authority: mint_authority.as_ref().map(|account| account.to_account_info()),
+            mint_authority_bump,
+            token_program: token_program.to_account_info(),
+        }.mint(pool, amount)?;
     }
-    user_info.reward_debt = I80F48::from_num(user_info.amount).checked_mul(pool.acc_reward_per_share.into()).ok_or_else(math_error!())?.into();
+    user_info.sync_reward_per_share(pool);
     emit!(DepositEvent {
         header: EventHeader {
             master_chef: master_chef_loader.key(),
@@ -98,6 +157,13 @@ pub fn deposit(ctx: Context<Deposit>, lp_token: Pubkey, amount: u64) -> Result<(
         },
         lp_token,
         amount,
+        user_amount: user_info.amount,
+        settled_reward,
+        accrued_reward: user_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+        position: None,
     });
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Transfer};
+
+use crate::{check, errors::MasterChefError, events::{DepositEvent, EventHeader}, state::MAX_BATCH_POOLS, utils::load_user_info, MasterChef, PoolGateMode};
+
+/// Accounts passed through `remaining_accounts` for every pool of `deposit_many`, in order:
+/// `user_info` (writable), `user_lp_token_account` (writable) and `lp_token_vault` (writable).
+pub const DEPOSIT_MANY_ACCOUNTS: usize = 3;
+
+#[derive(Accounts)]
+pub struct DepositMany<'info> {
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Deposits `amounts[i]` into the pool of `lp_tokens[i]` for each pool. Every `user_info`
+/// must already exist. Gated pools and pools with a receipt mint need more accounts and are
+/// rejected, they are deposited into with `deposit`.
+pub fn deposit_many<'info>(ctx: Context<'_, '_, '_, 'info, DepositMany<'info>>, lp_tokens: Vec<Pubkey>, amounts: Vec<u64>) -> Result<()> {
+    let DepositMany {
+        master_chef: master_chef_loader,
+        user,
+        token_program,
+    } = ctx.accounts;
+
+    check!(
+        lp_tokens.len() <= MAX_BATCH_POOLS,
+        MasterChefError::BatchTooLarge
+    );
+    check!(
+        amounts.len() == lp_tokens.len() && ctx.remaining_accounts.len() == lp_tokens.len() * DEPOSIT_MANY_ACCOUNTS,
+        MasterChefError::BatchAccountsMismatch
+    );
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let slot = Clock::get()?.slot;
+    for ((lp_token, amount), accounts) in lp_tokens.iter().zip(amounts).zip(ctx.remaining_accounts.chunks(DEPOSIT_MANY_ACCOUNTS)) {
+        let [user_info, user_lp_token_account, lp_token_vault] = accounts else {
+            return Err(MasterChefError::BatchAccountsMismatch.into());
+        };
+        let mut user_info = load_user_info(user_info, &user.key(), lp_token, &master_chef_loader.key())?;
+        let pool = master_chef.find_pool(lp_token)?;
+        check!(
+            !pool.is_closing(),
+            MasterChefError::PoolClosing
+        );
+        check!(
+            pool.gate_mode == u8::from(PoolGateMode::Open) && !pool.has_receipt_mint(),
+            MasterChefError::BatchUnsupportedPool
+        );
+        user_info.check_version()?;
+
+        pool.update_pool(master_chef_loader.key())?;
+
+        let mut settled_reward = 0;
+        if user_info.amount > 0 {
+            settled_reward = user_info.settle(pool)?;
+        }
+        if amount > 0 {
+            pool.check_deposit_caps(user_info.amount, amount)?;
+            pool.deposit_spl_transfer(
+                amount,
+                Transfer {
+                    from: user_lp_token_account.clone(),
+                    to: lp_token_vault.clone(),
+                    authority: user.to_account_info(),
+                },
+                token_program.to_account_info(),
+            )?;
+            user_info.amount += amount;
+            pool.lp_supply += amount;
+        }
+        user_info.sync_reward_per_share(pool);
+        user_info.exit(&crate::ID)?;
+
+        emit!(DepositEvent {
+            header: EventHeader {
+                master_chef: master_chef_loader.key(),
+                signer: Some(user.key()),
+            },
+            lp_token: *lp_token,
+            amount,
+            user_amount: user_info.amount,
+            settled_reward,
+            accrued_reward: user_info.accrued_reward,
+            lp_supply: pool.lp_supply,
+            acc_reward_per_share: pool.acc_reward_per_share,
+            slot,
+            position: None,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+use crate::{check, constants::*, errors::MasterChefError, events::{DepositEvent, EventHeader}, AllowlistEntry, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct DepositPosition<'info> {
+
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    /// CHECK: Seed constraint of `position_info` and mint of `position_token_account`
+    pub position_mint: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = position_mint,
+        token::authority = owner,
+        constraint = position_token_account.amount == 1 @ MasterChefError::NotPositionOwner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_info: Account<'info, UserInfo>,
+
+    /// CHECK: Token mint/authority are checked at transfer
+    #[account(mut)]
+    pub user_lp_token_account: AccountInfo<'info>,
+
+    /// Only required by allowlisted pools
+    #[account(
+        seeds = [
+            ALLOWLIST_SEED.as_bytes(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
+    pub lp_token_vault: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Stakes LP tokens into a position. Gating applies to the current NFT holder. Pools with a
+/// per user cap take no position deposits, since a holder could spread their stake over
+/// any number of positions.
+pub fn deposit_position(ctx: Context<DepositPosition>, lp_token: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let DepositPosition {
+        owner,
+        master_chef: master_chef_loader,
+        position_info,
+        position_mint,
+        user_lp_token_account,
+        allowlist_entry,
+        lp_token_vault,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
+    position_info.check_version()?;
+
+    pool.update_pool(master_chef_loader.key())?;
+
+    let mut settled_reward = 0;
+    if position_info.amount > 0 {
+        settled_reward = position_info.settle(pool)?;
+    }
+
+    if amount > 0 {
+        check!(
+            pool.unbonding_slots == 0,
+            MasterChefError::UnsupportedPositionPool,
+            "pool has an unbonding period of {} slots",
+            pool.unbonding_slots
+        );
+        check!(
+            pool.max_deposit_per_user == 0,
+            MasterChefError::UnsupportedPositionPool,
+            "pool caps deposits per user at {}",
+            pool.max_deposit_per_user
+        );
+        pool.check_gate(&owner.key(), allowlist_entry.is_some(), &proof)?;
+        pool.check_deposit_caps(position_info.amount, amount)?;
+        pool.deposit_spl_transfer(
+            amount,
+            Transfer {
+                from: user_lp_token_account.to_account_info(),
+                to: lp_token_vault.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+            token_program.to_account_info(),
+        )?;
+        position_info.amount += amount;
+        pool.lp_supply += amount;
+    }
+    position_info.sync_reward_per_share(pool);
+
+    emit!(DepositEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(owner.key()),
+        },
+        lp_token,
+        amount,
+        user_amount: position_info.amount,
+        settled_reward,
+        accrued_reward: position_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+        position: Some(position_mint.key()),
+    });
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{close_account, transfer, CloseAccount, Token, TokenAccount, Transfer};
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, PoolClosedEvent}, pool_signer, MasterChef, PoolVaultType};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct FinalizeClosePool<'info> {
+
+    #[account(
+        mut,
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    /// CHECK: Checked against the pool, already closed when finalizing again
+    #[account(mut)]
+    pub lp_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    pub lp_token_vault_authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub reward_token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    pub reward_token_vault_authority: AccountInfo<'info>,
+
+    /// Receives the rewards nobody is owed
+    #[account(
+        mut,
+        token::mint = reward_token_vault.mint,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    /// Receives LP tokens sent to the LP token vault without being staked, only required
+    /// when the vault holds any
+    #[account(
+        mut,
+        token::mint = lp_token,
+    )]
+    pub lp_treasury: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sweeps the rewards nobody is owed to `treasury`, moves LP tokens nobody staked to
+/// `lp_treasury` and closes the LP token vault. What stakers and referrers have not claimed
+/// yet stays in the reward vault, and the pool stays in its slot so they can still claim it
+/// and close their accounts. Once `reward_owed` and `referral_owed` are down to zero, this
+/// closes the reward vault and frees the slot, so it is called again until then. Past
+/// `CLAIM_DEADLINE_SLOTS` after `close_slot` whatever is still owed is forfeited to
+/// `treasury` as well, so the slot is always freed eventually.
+pub fn finalize_close_pool(ctx: Context<FinalizeClosePool>, lp_token: Pubkey) -> Result<()> {
+    let FinalizeClosePool {
+        admin,
+        master_chef: master_chef_loader,
+        lp_token_vault,
+        lp_token_vault_authority,
+        reward_token_vault,
+        reward_token_vault_authority,
+        treasury,
+        lp_treasury,
+        token_program,
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    pool.check_vault(PoolVaultType::LPTokenVault, &lp_token_vault.key())?;
+    pool.check_vault(PoolVaultType::RewardTokenVault, &reward_token_vault.key())?;
+    check!(
+        pool.is_closing(),
+        MasterChefError::PoolNotClosing
+    );
+    let slot = Clock::get()?.slot;
+    check!(
+        slot >= pool.close_slot,
+        MasterChefError::GracePeriodNotOver
+    );
+    check!(
+        pool.lp_supply == 0 && pool.cooling_supply == 0,
+        MasterChefError::PoolNotEmpty
+    );
+
+    pool.release_undistributed_reward()?;
+    let forfeited_reward = pool.forfeit_unclaimed_reward(slot)?;
+    let owed = pool.total_owed()?;
+    let returned_reward = pool.excess_reward(reward_token_vault.amount)?;
+    let reward_signer: &[&[&[u8]]] = pool_signer!(PoolVaultType::RewardTokenVault, lp_token, pool.reward_token_vault_authority_bump, master_chef_loader.key());
+    if returned_reward > 0 {
+        pool.withdraw_sql_transfer(
+            returned_reward,
+            Transfer {
+                from: reward_token_vault.to_account_info(),
+                to: treasury.to_account_info(),
+                authority: reward_token_vault_authority.to_account_info(),
+            },
+            token_program.to_account_info(),
+            reward_signer,
+        )?;
+    }
+    let mut returned_lp = 0;
+    if !lp_token_vault.data_is_empty() {
+        let lp_signer: &[&[&[u8]]] = pool_signer!(PoolVaultType::LPTokenVault, lp_token, pool.lp_token_vault_authority_bump, master_chef_loader.key());
+        // nothing is staked or cooling anymore, so every LP token left was sent to the vault
+        // directly and would keep `close_account` from closing it
+        returned_lp = TokenAccount::try_deserialize(&mut &lp_token_vault.try_borrow_data()?[..])?.amount;
+        if returned_lp > 0 {
+            let lp_treasury = lp_treasury.as_ref().ok_or(MasterChefError::LpTreasuryMissing)?;
+            transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: lp_token_vault.to_account_info(),
+                        to: lp_treasury.to_account_info(),
+                        authority: lp_token_vault_authority.to_account_info(),
+                    },
+                    lp_signer,
+                ),
+                returned_lp,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: lp_token_vault.to_account_info(),
+                destination: admin.to_account_info(),
+                authority: lp_token_vault_authority.to_account_info(),
+            },
+            lp_signer,
+        ))?;
+    }
+    if owed == 0 {
+        close_account(CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            CloseAccount {
+                account: reward_token_vault.to_account_info(),
+                destination: admin.to_account_info(),
+                authority: reward_token_vault_authority.to_account_info(),
+            },
+            reward_signer,
+        ))?;
+        pool.initialized = false;
+        pool.close_slot = 0;
+    }
+
+    emit!(PoolClosedEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(admin.key()),
+        },
+        lp_token,
+        treasury: treasury.key(),
+        returned_reward,
+        forfeited_reward,
+        returned_lp,
+        reward_owed: pool.reward_owed,
+        referral_owed: pool.referral_owed,
+        slot_freed: !pool.initialized,
+    });
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    check,
+    constants::ACCOUNT_VERSION,
+    errors::MasterChefError,
+    events::{EventHeader, MasterChefMigratedEvent},
+    legacy::{fixed_to_scaled, MasterChefV0},
+    math_error,
+    state::{MasterChef, PoolVaultType, MAX_POOLS},
+    utils::resize_account,
+};
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Older layouts cannot be loaded as `MasterChef`, discriminator and admin are checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID,
+    )]
+    pub master_chef: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrades `master_chef` to the current layout. Version 0 did not track `reward_owed`, so
+/// the reward vault of every initialized pool has to follow as a remaining account, in pool
+/// order, and its whole balance is taken as owed: `withdraw_excess_rewards` only returns
+/// what stakers cannot claim anymore.
+pub fn migrate<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+    let Migrate {
+        admin,
+        master_chef,
+        system_program,
+    } = ctx.accounts;
+
+    let (from_version, legacy_data) = {
+        let data = master_chef.try_borrow_data()?;
+        check!(
+            data.len() > 8 && data[..8] == MasterChef::discriminator(),
+            MasterChefError::UnknownAccountVersion
+        );
+        // `admin` is the first field of every layout
+        check!(
+            data[8..40] == admin.key().to_bytes(),
+            MasterChefError::Unauthorized
+        );
+        match data.len() - 8 {
+            MasterChefV0::LEN => (0, data[8..].to_vec()),
+            len if len == std::mem::size_of::<MasterChef>() => {
+                (bytemuck::from_bytes::<MasterChef>(&data[8..]).version, Vec::new())
+            }
+            _ => return Err(MasterChefError::UnknownAccountVersion.into()),
+        }
+    };
+    check!(
+        from_version < ACCOUNT_VERSION,
+        MasterChefError::AlreadyMigrated
+    );
+
+    resize_account(
+        &master_chef.to_account_info(),
+        &admin.to_account_info(),
+        &admin.to_account_info(),
+        &system_program.to_account_info(),
+        8 + std::mem::size_of::<MasterChef>(),
+    )?;
+
+    {
+        let mut data = master_chef.try_borrow_mut_data()?;
+        if from_version == 0 {
+            // pools moved, so rebuild the body from the copy taken above
+            data[8..].fill(0);
+        }
+        let master_chef: &mut MasterChef = bytemuck::from_bytes_mut(&mut data[8..]);
+        if from_version == 0 {
+            master_chef.admin = admin.key();
+            for index in 0..MAX_POOLS {
+                MasterChefV0::read_pool(&legacy_data, index)
+                    .migrate_into(&mut master_chef.pools[index])
+                    .ok_or_else(math_error!())?;
+            }
+            let mut reward_token_vaults = ctx.remaining_accounts.iter();
+            for pool in master_chef.pools.iter_mut().filter(|pool| pool.initialized) {
+                let reward_token_vault = reward_token_vaults.next().ok_or(MasterChefError::InvalidPoolVault)?;
+                pool.check_vault(PoolVaultType::RewardTokenVault, reward_token_vault.key)?;
+                pool.reward_owed = Account::<TokenAccount>::try_from(reward_token_vault)?.amount;
+            }
+        } else {
+            // version 1 kept the accumulator as `I80F48` bits
+            for pool in master_chef.pools.iter_mut() {
+                pool.acc_reward_per_share =
+                    fixed_to_scaled(pool.acc_reward_per_share as i128, false)
+                        .ok_or_else(math_error!())?;
+            }
+        }
+        for pool in master_chef.pools.iter_mut() {
+            pool.version = ACCOUNT_VERSION;
+        }
+        master_chef.version = ACCOUNT_VERSION;
+    }
+
+    emit!(MasterChefMigratedEvent {
+        header: EventHeader {
+            master_chef: master_chef.key(),
+            signer: Some(admin.key()),
+        },
+        from_version,
+        to_version: ACCOUNT_VERSION,
+    });
+    Ok(())
+}
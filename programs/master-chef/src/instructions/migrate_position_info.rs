@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    check,
+    constants::{ACCOUNT_VERSION, POSITION_SEED},
+    errors::MasterChefError,
+    events::{EventHeader, UserInfoMigratedEvent},
+    instructions::migrate_user_info::migrate_reward_fields,
+    state::{MasterChef, UserInfo},
+};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct MigratePositionInfo<'info> {
+    pub signer: Signer<'info>,
+
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    /// CHECK: Seed of `position_info`
+    pub position_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_info: Account<'info, UserInfo>,
+}
+
+/// Converts a position opened before the current version. Positions always had the
+/// current size, so unlike `migrate_user_info` nothing is resized and anyone can call it.
+/// The event reports the position mint as `user`.
+pub fn migrate_position_info(ctx: Context<MigratePositionInfo>, lp_token: Pubkey) -> Result<()> {
+    let MigratePositionInfo {
+        signer,
+        master_chef,
+        position_mint,
+        position_info,
+    } = ctx.accounts;
+
+    let from_version = position_info.version;
+    check!(
+        from_version < ACCOUNT_VERSION,
+        MasterChefError::AlreadyMigrated
+    );
+    migrate_reward_fields(position_info, &*master_chef.load()?, &lp_token)?;
+    position_info.version = ACCOUNT_VERSION;
+
+    emit!(UserInfoMigratedEvent {
+        header: EventHeader {
+            master_chef: master_chef.key(),
+            signer: Some(signer.key()),
+        },
+        user: position_mint.key(),
+        lp_token,
+        from_version,
+        to_version: ACCOUNT_VERSION,
+    });
+    Ok(())
+}
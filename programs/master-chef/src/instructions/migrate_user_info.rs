@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    check,
+    constants::ACCOUNT_VERSION,
+    errors::MasterChefError,
+    events::{EventHeader, UserInfoMigratedEvent},
+    legacy::migrate_fixed_user_info,
+    math_error,
+    state::{MasterChef, UserInfo},
+    utils::resize_account,
+};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct MigrateUserInfo<'info> {
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Bound to `user_info` by its seeds, only receives the rent surplus
+    #[account(mut)]
+    pub user: UncheckedAccount<'info>,
+
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        mut,
+        seeds = [
+            user.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user_info(ctx: Context<MigrateUserInfo>, lp_token: Pubkey) -> Result<()> {
+    let MigrateUserInfo {
+        payer,
+        user,
+        master_chef,
+        user_info,
+        system_program,
+    } = ctx.accounts;
+
+    let from_version = user_info.version;
+    check!(
+        from_version < ACCOUNT_VERSION || user_info.to_account_info().data_len() != UserInfo::LEN,
+        MasterChefError::AlreadyMigrated
+    );
+
+    resize_account(
+        &user_info.to_account_info(),
+        &payer.to_account_info(),
+        &user.to_account_info(),
+        &system_program.to_account_info(),
+        UserInfo::LEN,
+    )?;
+    if from_version < ACCOUNT_VERSION {
+        migrate_reward_fields(user_info, &*master_chef.load()?, &lp_token)?;
+    }
+    user_info.version = ACCOUNT_VERSION;
+
+    emit!(UserInfoMigratedEvent {
+        header: EventHeader {
+            master_chef: master_chef.key(),
+            signer: Some(payer.key()),
+        },
+        user: user.key(),
+        lp_token,
+        from_version,
+        to_version: ACCOUNT_VERSION,
+    });
+    Ok(())
+}
+
+/// Converts the `I80F48` reward fields of a version 1 or earlier user or position account,
+/// once its pool has been migrated.
+pub(crate) fn migrate_reward_fields(
+    user_info: &mut UserInfo,
+    master_chef: &MasterChef,
+    lp_token: &Pubkey,
+) -> Result<()> {
+    check!(
+        master_chef.version == ACCOUNT_VERSION,
+        MasterChefError::AccountNotMigrated
+    );
+    // a finalized pool has no stakers left, so its users only carry accrued rewards
+    let acc_reward_per_share = match master_chef
+        .pools
+        .iter()
+        .find(|pool| pool.initialized && pool.lp_token == *lp_token)
+    {
+        Some(pool) => pool.acc_reward_per_share,
+        None if user_info.amount == 0 => 0,
+        None => return Err(MasterChefError::PoolNotFind.into()),
+    };
+    migrate_fixed_user_info(user_info, acc_reward_per_share).ok_or_else(math_error!())?;
+    Ok(())
+}
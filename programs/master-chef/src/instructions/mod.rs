@@ -5,6 +5,33 @@ pub mod add_pool;
 pub mod deposit;
 pub mod withdraw;
 pub mod claim_reward;
+pub mod migrate;
+pub mod migrate_position_info;
+pub mod migrate_user_info;
+pub mod close_user_info;
+pub mod close_pool;
+pub mod finalize_close_pool;
+pub mod withdraw_excess_rewards;
+pub mod rescue_tokens;
+pub mod configure_pool;
+pub mod add_to_allowlist;
+pub mod remove_from_allowlist;
+pub mod request_withdraw;
+pub mod complete_withdraw;
+pub mod register_referrer;
+pub mod claim_referral_reward;
+pub mod set_emission_schedule;
+pub mod open_position;
+pub mod deposit_position;
+pub mod withdraw_position;
+pub mod claim_position_reward;
+pub mod close_position;
+pub mod transfer_position;
+pub mod create_receipt_mint;
+pub mod claim_all;
+pub mod deposit_many;
+pub mod sweep_reward_dust;
+pub mod audit_pool;
 
 pub use initialize::*;
 pub use set_admin::*;
@@ -12,4 +39,31 @@ pub use update_reward_per_slot::*;
 pub use add_pool::*;
 pub use deposit::*;
 pub use withdraw::*;
-pub use claim_reward::*;
\ No newline at end of file
+pub use claim_reward::*;
+pub use migrate::*;
+pub use migrate_position_info::*;
+pub use migrate_user_info::*;
+pub use close_user_info::*;
+pub use close_pool::*;
+pub use finalize_close_pool::*;
+pub use withdraw_excess_rewards::*;
+pub use rescue_tokens::*;
+pub use configure_pool::*;
+pub use add_to_allowlist::*;
+pub use remove_from_allowlist::*;
+pub use request_withdraw::*;
+pub use complete_withdraw::*;
+pub use register_referrer::*;
+pub use claim_referral_reward::*;
+pub use set_emission_schedule::*;
+pub use open_position::*;
+pub use deposit_position::*;
+pub use withdraw_position::*;
+pub use claim_position_reward::*;
+pub use close_position::*;
+pub use transfer_position::*;
+pub use create_receipt_mint::*;
+pub use claim_all::*;
+pub use deposit_many::*;
+pub use sweep_reward_dust::*;
+pub use audit_pool::*;
\ No newline at end of file
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, set_authority, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority, Token, TokenAccount},
+};
+
+use crate::{check, constants::*, errors::MasterChefError, events::{EventHeader, PositionOpenedEvent}, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct OpenPosition<'info> {
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = mint_authority,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = UserInfo::LEN,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_info: Account<'info, UserInfo>,
+
+    /// CHECK: Seed constraint check
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an empty staking position owned by whoever holds its NFT. The NFT is minted to
+/// the owner and the mint authority is dropped right away, so exactly one token ever exists.
+pub fn open_position(ctx: Context<OpenPosition>, lp_token: Pubkey) -> Result<()> {
+    let mint_authority_bump = *ctx.bumps.get("mint_authority").unwrap();
+    let OpenPosition {
+        owner,
+        master_chef: master_chef_loader,
+        position_mint,
+        position_token_account,
+        position_info,
+        mint_authority,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
+
+    let master_chef_key = master_chef_loader.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        MINT_AUTHORITY_SEED.as_bytes(),
+        master_chef_key.as_ref(),
+        &[mint_authority_bump],
+    ]];
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: position_mint.to_account_info(),
+                to: position_token_account.to_account_info(),
+                authority: mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+    set_authority(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            SetAuthority {
+                current_authority: mint_authority.to_account_info(),
+                account_or_mint: position_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    position_info.version = ACCOUNT_VERSION;
+
+    emit!(PositionOpenedEvent {
+        header: EventHeader {
+            master_chef: master_chef_key,
+            signer: Some(owner.key()),
+        },
+        lp_token,
+        position_mint: position_mint.key(),
+    });
+    Ok(())
+}
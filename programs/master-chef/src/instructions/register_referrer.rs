@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, events::{EventHeader, RegisterReferrerEvent}, MasterChef, ReferrerInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct RegisterReferrer<'info> {
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = ReferrerInfo::LEN,
+        seeds = [
+            REFERRER_SEED.as_bytes(),
+            referrer.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub referrer_info: Account<'info, ReferrerInfo>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>, lp_token: Pubkey) -> Result<()> {
+    ctx.accounts.master_chef.load_mut()?.find_pool(&lp_token)?;
+
+    let referrer_info = &mut ctx.accounts.referrer_info;
+    referrer_info.referrer = ctx.accounts.referrer.key();
+    referrer_info.bump = *ctx.bumps.get("referrer_info").unwrap();
+
+    emit!(RegisterReferrerEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.referrer.key()),
+        },
+        lp_token,
+    });
+    Ok(())
+}
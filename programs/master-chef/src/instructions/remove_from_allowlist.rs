@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, events::{AllowlistUpdatedEvent, EventHeader}, AllowlistEntry, MasterChef};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey, user: Pubkey)]
+pub struct RemoveFromAllowlist<'info> {
+
+    #[account(
+        mut,
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            ALLOWLIST_SEED.as_bytes(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+            user.as_ref(),
+        ],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+}
+
+/// Revokes deposit access. Existing stake stays untouched and can always be withdrawn.
+pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>, lp_token: Pubkey, user: Pubkey) -> Result<()> {
+    emit!(AllowlistUpdatedEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.admin.key()),
+        },
+        lp_token,
+        user,
+        allowed: false,
+    });
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{check, errors::*, events::{EventHeader, WithdrawRequestedEvent}, math_error, utils::ReceiptAccounts, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct RequestWithdraw<'info> {
+
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        mut,
+        seeds = [
+            user.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    /// CHECK: Checked against the pool, only required by pools with a receipt mint
+    #[account(mut)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token mint/authority are checked at burn
+    #[account(mut)]
+    pub user_receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` out of the user's stake into the cooling state. Cooling tokens stop earning
+/// right away and can be taken out with `complete_withdraw` once the pool's unbonding period
+/// has elapsed. A new request restarts the cooldown of everything already cooling.
+pub fn request_withdraw(ctx: Context<RequestWithdraw>, lp_token: Pubkey, amount: u64) -> Result<()> {
+    let RequestWithdraw {
+        user,
+        master_chef: master_chef_loader,
+        user_info,
+        receipt_mint,
+        user_receipt_token_account,
+        token_program,
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+
+    check!(
+        user_info.amount >= amount,
+        MasterChefError::LPTokenNotEnough
+    );
+    pool.update_pool(master_chef_loader.key())?;
+
+    let pending = user_info.settle(pool)?;
+
+    let slot = Clock::get()?.slot;
+    if amount > 0 {
+        ReceiptAccounts {
+            master_chef: master_chef_loader.key(),
+            receipt_mint: receipt_mint.as_ref().map(|account| account.to_account_info()),
+            receipt_token_account: user_receipt_token_account.as_ref().map(|account| account.to_account_info()),
+            mint_authority: None,
+            mint_authority_bump: None,
+            token_program: token_program.to_account_info(),
+        }.burn(pool, user.to_account_info(), amount)?;
+        user_info.amount -= amount;
+        pool.lp_supply -= amount;
+        user_info.cooling_amount = user_info.cooling_amount.checked_add(amount).ok_or_else(math_error!())?;
+        pool.cooling_supply = pool.cooling_supply.checked_add(amount).ok_or_else(math_error!())?;
+        user_info.cooldown_end_slot = slot.checked_add(pool.unbonding_slots).ok_or_else(math_error!())?;
+    }
+
+    user_info.sync_reward_per_share(pool);
+
+    emit!(WithdrawRequestedEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(user.key()),
+        },
+        lp_token,
+        amount,
+        user_amount: user_info.amount,
+        cooling_amount: user_info.cooling_amount,
+        cooldown_end_slot: user_info.cooldown_end_slot,
+        settled_reward: pending,
+        accrued_reward: user_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, RescueTokensEvent}, pool_signer, MasterChef, PoolVaultType};
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+
+    #[account(
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    /// CHECK: Checked against the pool's vault authority PDA in the handler
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::authority = vault_authority,
+    )]
+    pub source: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = source.mint,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves tokens sent by mistake to an account owned by one of the pool's vault
+/// authorities. The registered LP and reward vaults can never be drained this way.
+pub fn rescue_tokens(ctx: Context<RescueTokens>, lp_token: Pubkey, vault_type: PoolVaultType, amount: u64) -> Result<()> {
+    let RescueTokens {
+        admin,
+        master_chef: master_chef_loader,
+        vault_authority,
+        source,
+        destination,
+        token_program,
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+
+    let authority_bump = match vault_type {
+        PoolVaultType::LPTokenVault => pool.lp_token_vault_authority_bump,
+        PoolVaultType::RewardTokenVault => pool.reward_token_vault_authority_bump,
+    };
+    let expected_authority = Pubkey::create_program_address(
+        &[
+            vault_type.get_authority_seed(),
+            lp_token.as_ref(),
+            master_chef_loader.key().as_ref(),
+            &[authority_bump],
+        ],
+        &crate::ID,
+    ).map_err(|_| MasterChefError::InvalidVaultAuthority)?;
+    check!(
+        vault_authority.key() == expected_authority,
+        MasterChefError::InvalidVaultAuthority
+    );
+    check!(
+        source.key() != pool.lp_token_vault && source.key() != pool.reward_token_vault,
+        MasterChefError::CannotRescuePoolVault
+    );
+
+    pool.withdraw_sql_transfer(
+        amount,
+        Transfer {
+            from: source.to_account_info(),
+            to: destination.to_account_info(),
+            authority: vault_authority.to_account_info(),
+        },
+        token_program.to_account_info(),
+        pool_signer!(vault_type, lp_token, authority_bump, master_chef_loader.key()),
+    )?;
+
+    emit!(RescueTokensEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(admin.key()),
+        },
+        lp_token,
+        vault_type,
+        source: source.key(),
+        destination: destination.key(),
+        mint: source.mint,
+        amount,
+    });
+    Ok(())
+}
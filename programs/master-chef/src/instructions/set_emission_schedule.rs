@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, SetEmissionScheduleEvent}, state::*};
+
+#[derive(Accounts)]
+pub struct SetEmissionSchedule<'info> {
+
+    #[account(
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+}
+
+/// Replaces the emission schedule of a pool. Rewards up to the current slot are settled
+/// with the previous schedule first, so only future emissions follow the new one.
+pub fn set_emission_schedule(
+    ctx: Context<SetEmissionSchedule>,
+    lp_token: Pubkey,
+    segments: Vec<EmissionSegment>,
+    halving_period: u64,
+) -> Result<()> {
+    let mut master_chef = ctx.accounts.master_chef.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
+    pool.update_pool(ctx.accounts.master_chef.key())?;
+    pool.set_emission_schedule(&segments, halving_period)?;
+
+    emit!(SetEmissionScheduleEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.admin.key()),
+        },
+        lp_token,
+        segments,
+        halving_period,
+    });
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, SweepRewardDustEvent}, math_error, MasterChef};
+
+#[derive(Accounts)]
+pub struct SweepRewardDust<'info> {
+
+    #[account(
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+}
+
+/// Releases the whole tokens collected in a pool's `reward_dust` from `reward_owed`. Vault
+/// mode pools can then return them with `withdraw_excess_rewards`, mint mode pools never
+/// mint them.
+pub fn sweep_reward_dust(ctx: Context<SweepRewardDust>, lp_token: Pubkey) -> Result<()> {
+    let mut master_chef = ctx.accounts.master_chef.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+
+    let amount = pool.take_reward_dust()?;
+    check!(
+        amount > 0,
+        MasterChefError::NoRewardDust
+    );
+    pool.reward_owed = pool.reward_owed.checked_sub(amount).ok_or_else(math_error!())?;
+
+    emit!(SweepRewardDustEvent {
+        header: EventHeader {
+            master_chef: ctx.accounts.master_chef.key(),
+            signer: Some(ctx.accounts.admin.key()),
+        },
+        lp_token,
+        amount,
+        reward_dust: pool.reward_dust,
+        reward_owed: pool.reward_owed,
+    });
+    Ok(())
+}
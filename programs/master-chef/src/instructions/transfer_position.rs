@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{check, constants::*, errors::MasterChefError, events::{EventHeader, TransferPositionEvent}, AllowlistEntry, utils::ReceiptAccounts, MasterChef, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct TransferPosition<'info> {
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Only used as a seed of `recipient_info`
+    #[account(constraint = recipient.key() != user.key() @ MasterChefError::CannotTransferToSelf)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(
+        mut,
+        seeds = [
+            user.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub user_info: Account<'info, UserInfo>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserInfo::LEN,
+        seeds = [
+            recipient.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub recipient_info: Account<'info, UserInfo>,
+
+    /// Only required by allowlisted pools
+    #[account(
+        seeds = [
+            ALLOWLIST_SEED.as_bytes(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump = allowlist_entry.bump,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// CHECK: Checked against the pool, only required by pools with a receipt mint
+    #[account(mut)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token mint/authority are checked at burn
+    #[account(mut)]
+    pub user_receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token mint is checked at mint
+    #[account(mut)]
+    pub recipient_receipt_token_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Seed constraint check, only required by pools with a receipt mint
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves staked LP tokens to another wallet without going through the vault. Rewards
+/// pending up to now stay claimable by the sender, cooling tokens stay where they are and
+/// the sender's referrer is not carried over. In pools with a receipt mint the sender's
+/// receipt tokens are burned and the same amount is minted to the recipient.
+pub fn transfer_position(ctx: Context<TransferPosition>, lp_token: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let mint_authority_bump = ctx.bumps.get("mint_authority").copied();
+    let TransferPosition {
+        user,
+        recipient,
+        master_chef: master_chef_loader,
+        user_info,
+        recipient_info,
+        allowlist_entry,
+        receipt_mint,
+        user_receipt_token_account,
+        recipient_receipt_token_account,
+        mint_authority,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
+    check!(
+        user_info.amount >= amount,
+        MasterChefError::LPTokenNotEnough
+    );
+    pool.check_gate(&recipient.key(), allowlist_entry.is_some(), &proof)?;
+    pool.check_user_deposit_cap(recipient_info.amount, amount)?;
+    user_info.check_version()?;
+    recipient_info.check_version()?;
+
+    pool.update_pool(master_chef_loader.key())?;
+
+    let user_settled_reward = user_info.settle(pool)?;
+    let recipient_settled_reward = recipient_info.settle(pool)?;
+    user_info.amount -= amount;
+    recipient_info.amount += amount;
+    let sender_receipt_accounts = ReceiptAccounts {
+        master_chef: master_chef_loader.key(),
+        receipt_mint: receipt_mint.as_ref().map(|account| account.to_account_info()),
+        receipt_token_account: user_receipt_token_account.as_ref().map(|account| account.to_account_info()),
+        mint_authority: mint_authority.as_ref().map(|account| account.to_account_info()),
+        mint_authority_bump,
+        token_program: token_program.to_account_info(),
+    };
+    sender_receipt_accounts.burn(pool, user.to_account_info(), amount)?;
+    ReceiptAccounts {
+        receipt_token_account: recipient_receipt_token_account.as_ref().map(|account| account.to_account_info()),
+        ..sender_receipt_accounts
+    }.mint(pool, amount)?;
+    user_info.sync_reward_per_share(pool);
+    recipient_info.sync_reward_per_share(pool);
+
+    emit!(TransferPositionEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(user.key()),
+        },
+        lp_token,
+        recipient: recipient.key(),
+        amount,
+        user_amount: user_info.amount,
+        recipient_amount: recipient_info.amount,
+        user_settled_reward,
+        user_accrued_reward: user_info.accrued_reward,
+        recipient_settled_reward,
+        recipient_accrued_reward: recipient_info.accrued_reward,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+    });
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{events::{EventHeader, UpadteRewardPerSlotEvent}, state::*};
+use crate::{check, errors::MasterChefError, events::{EventHeader, UpadteRewardPerSlotEvent}, state::*};
 
 #[derive(Accounts)]
 pub struct UpadteRewardPerSlot<'info> {
@@ -21,8 +21,12 @@ pub fn update_reward_per_slot(ctx: Context<UpadteRewardPerSlot>, lp_token: Pubke
 
     let mut master_chef = ctx.accounts.master_chef.load_mut()?;
     let pool = master_chef.find_pool(&lp_token)?;
+    check!(
+        !pool.is_closing(),
+        MasterChefError::PoolClosing
+    );
     let old_reward_per_slot = pool.reward_per_slot;
-    pool.update_pool()?;
+    pool.update_pool(ctx.accounts.master_chef.key())?;
     pool.reward_per_slot = new_reward_per_slot;
 
     emit!(UpadteRewardPerSlotEvent {
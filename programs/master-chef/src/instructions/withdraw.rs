@@ -1,8 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, Transfer};
-use fixed::types::I80F48;
 
-use crate::{constants::*, check, constants::LP_TOKEN_VAULT_AUTHORITY_SEED, errors::*, events::{EventHeader, WithdrawEvent}, math_error, pool_signer, MasterChef, PoolVaultType, UserInfo};
+use crate::{check, errors::*, events::{EventHeader, WithdrawEvent}, pool_signer, utils::ReceiptAccounts, MasterChef, PoolVaultType, UserInfo};
 
 #[derive(Accounts)]
 #[instruction(lp_token: Pubkey)]
@@ -19,6 +18,7 @@ pub struct Withdraw<'info> {
     pub user_lp_token_account: AccountInfo<'info>,
 
     #[account(
+        mut,
         seeds = [
             user.key().as_ref(),
             lp_token.as_ref(),
@@ -28,34 +28,22 @@ pub struct Withdraw<'info> {
     )]
     pub user_info: Account<'info, UserInfo>,
 
-    /// CHECK: Seed constraint check
-    #[
-        account(
-            mut,
-            seeds = [
-                LP_TOKEN_VAULT_SEED.as_bytes(),
-                lp_token.key().as_ref(),
-                master_chef.key().as_ref(),
-            ],
-            bump = master_chef.load_mut()?.find_pool(&lp_token)?.lp_token_vault_bump,
-        )
-    ]
+    /// CHECK: Checked against the pool
+    #[account(mut)]
     pub lp_token_vault: AccountInfo<'info>,
 
-    /// CHECK: Seed constraint check
-    #[
-        account(
-            mut,
-            seeds = [
-                LP_TOKEN_VAULT_AUTHORITY_SEED.as_bytes(),
-                lp_token.as_ref(),
-                master_chef.key().as_ref(),
-            ],
-            bump = master_chef.load_mut()?.find_pool(&lp_token)?.lp_token_vault_authority_bump,
-        )
-    ]
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    #[account(mut)]
     pub lp_token_vault_authority: AccountInfo<'info>,
 
+    /// CHECK: Checked against the pool, only required by pools with a receipt mint
+    #[account(mut)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Token mint/authority are checked at burn
+    #[account(mut)]
+    pub user_receipt_token_account: Option<UncheckedAccount<'info>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -69,28 +57,37 @@ pub fn withdraw(ctx: Context<Withdraw>, lp_token: Pubkey, amount: u64) -> Result
         lp_token_vault,
         lp_token_vault_authority,
         token_program,
+        receipt_mint,
+        user_receipt_token_account,
         ..
     }
     = ctx.accounts;
 
     let mut master_chef = master_chef_loader.load_mut()?;
     let pool = master_chef.find_pool(&lp_token)?;
+    pool.check_vault(PoolVaultType::LPTokenVault, &lp_token_vault.key())?;
 
+    check!(
+        pool.unbonding_slots == 0 || pool.is_closing(),
+        MasterChefError::UnbondingRequired
+    );
     check!(
         user_info.amount >= amount,
         MasterChefError::LPTokenNotEnough
     );
-    pool.update_pool()?;
-
-    let pending = I80F48::from_num(user_info.amount)
-        .checked_mul(pool.acc_reward_per_share
-        .into())
-        .ok_or_else(math_error!())?
-        .checked_sub(user_info.reward_debt.into())
-        .ok_or_else(math_error!())?;
-    user_info.accrued_reward = pending.checked_add(user_info.accrued_reward.into()).ok_or_else(math_error!())?.into();
+    pool.update_pool(master_chef_loader.key())?;
+
+    let pending = user_info.settle(pool)?;
     
     if amount > 0 {
+        ReceiptAccounts {
+            master_chef: master_chef_loader.key(),
+            receipt_mint: receipt_mint.as_ref().map(|account| account.to_account_info()),
+            receipt_token_account: user_receipt_token_account.as_ref().map(|account| account.to_account_info()),
+            mint_authority: None,
+            mint_authority_bump: None,
+            token_program: token_program.to_account_info(),
+        }.burn(pool, user.to_account_info(), amount)?;
         pool.withdraw_sql_transfer(
             amount, 
             Transfer {
@@ -105,7 +102,7 @@ pub fn withdraw(ctx: Context<Withdraw>, lp_token: Pubkey, amount: u64) -> Result
         pool.lp_supply -= amount;
     }
 
-    user_info.reward_debt = I80F48::from_num(user_info.amount).checked_mul(pool.acc_reward_per_share.into()).ok_or_else(math_error!())?.into();
+    user_info.sync_reward_per_share(pool);
 
     emit!(WithdrawEvent {
         header: EventHeader {
@@ -114,6 +111,13 @@ pub fn withdraw(ctx: Context<Withdraw>, lp_token: Pubkey, amount: u64) -> Result
         },
         lp_token,
         amount,
+        user_amount: user_info.amount,
+        settled_reward: pending,
+        accrued_reward: user_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+        position: None,
     });
 
     Ok(())
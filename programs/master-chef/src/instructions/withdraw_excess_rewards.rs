@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+use crate::{check, errors::MasterChefError, events::{EventHeader, WithdrawExcessRewardsEvent}, pool_signer, MasterChef, PoolVaultType};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct WithdrawExcessRewards<'info> {
+
+    #[account(
+        address = master_chef.load()?.admin,
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    #[account(mut)]
+    pub reward_token_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    pub reward_token_vault_authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = reward_token_vault.mint,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sweeps up to `amount` of the reward vault balance that is not owed to stakers, any of
+/// it in mint mode pools since those mint what they owe.
+/// Rewards still to be emitted in the future are not reserved, so the vault should be
+/// topped up again before sweeping it down to what is currently owed.
+pub fn withdraw_excess_rewards(ctx: Context<WithdrawExcessRewards>, lp_token: Pubkey, amount: u64) -> Result<()> {
+    let WithdrawExcessRewards {
+        admin,
+        master_chef: master_chef_loader,
+        reward_token_vault,
+        reward_token_vault_authority,
+        treasury,
+        token_program,
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    pool.check_vault(PoolVaultType::RewardTokenVault, &reward_token_vault.key())?;
+    pool.update_pool(master_chef_loader.key())?;
+
+    let excess = pool.excess_reward(reward_token_vault.amount)?;
+    check!(
+        amount <= excess,
+        MasterChefError::InsufficientExcessRewards,
+        "excess rewards: {}, requested: {}",
+        excess,
+        amount
+    );
+
+    pool.withdraw_sql_transfer(
+        amount,
+        Transfer {
+            from: reward_token_vault.to_account_info(),
+            to: treasury.to_account_info(),
+            authority: reward_token_vault_authority.to_account_info(),
+        },
+        token_program.to_account_info(),
+        pool_signer!(PoolVaultType::RewardTokenVault, lp_token, pool.reward_token_vault_authority_bump, master_chef_loader.key()),
+    )?;
+
+    emit!(WithdrawExcessRewardsEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(admin.key()),
+        },
+        lp_token,
+        treasury: treasury.key(),
+        amount,
+        reward_owed: pool.reward_owed,
+    });
+    Ok(())
+}
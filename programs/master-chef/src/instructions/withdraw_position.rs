@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Transfer};
+
+use crate::{check, constants::*, errors::MasterChefError, events::{EventHeader, WithdrawEvent}, pool_signer, MasterChef, PoolVaultType, UserInfo};
+
+#[derive(Accounts)]
+#[instruction(lp_token: Pubkey)]
+pub struct WithdrawPosition<'info> {
+
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub master_chef: AccountLoader<'info, MasterChef>,
+
+    /// CHECK: Seed constraint of `position_info` and mint of `position_token_account`
+    pub position_mint: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = position_mint,
+        token::authority = owner,
+        constraint = position_token_account.amount == 1 @ MasterChefError::NotPositionOwner,
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POSITION_SEED.as_bytes(),
+            position_mint.key().as_ref(),
+            lp_token.as_ref(),
+            master_chef.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub position_info: Account<'info, UserInfo>,
+
+    /// CHECK: Token mint/authority are checked at transfer
+    #[account(mut)]
+    pub user_lp_token_account: AccountInfo<'info>,
+
+    /// CHECK: Checked against the pool
+    #[account(mut)]
+    pub lp_token_vault: AccountInfo<'info>,
+
+    /// CHECK: Signs through `pool_signer!`, so the CPI fails for any other account
+    pub lp_token_vault_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Unstakes LP tokens from a position to the NFT holder. Positions have no cooling state,
+/// they cannot be staked into pools with an unbonding period and are always withdrawn
+/// directly, even when the pool gets one later.
+pub fn withdraw_position(ctx: Context<WithdrawPosition>, lp_token: Pubkey, amount: u64) -> Result<()> {
+    let WithdrawPosition {
+        owner,
+        master_chef: master_chef_loader,
+        position_info,
+        position_mint,
+        user_lp_token_account,
+        lp_token_vault,
+        lp_token_vault_authority,
+        token_program,
+        ..
+    } = ctx.accounts;
+
+    let mut master_chef = master_chef_loader.load_mut()?;
+    let pool = master_chef.find_pool(&lp_token)?;
+    pool.check_vault(PoolVaultType::LPTokenVault, &lp_token_vault.key())?;
+    check!(
+        position_info.amount >= amount,
+        MasterChefError::LPTokenNotEnough
+    );
+    pool.update_pool(master_chef_loader.key())?;
+
+    let pending = position_info.settle(pool)?;
+
+    if amount > 0 {
+        pool.withdraw_sql_transfer(
+            amount,
+            Transfer {
+                from: lp_token_vault.to_account_info(),
+                to: user_lp_token_account.to_account_info(),
+                authority: lp_token_vault_authority.to_account_info(),
+            },
+            token_program.to_account_info(),
+            pool_signer!(PoolVaultType::LPTokenVault, lp_token, pool.lp_token_vault_authority_bump, master_chef_loader.key())
+        )?;
+        position_info.amount -= amount;
+        pool.lp_supply -= amount;
+    }
+    position_info.sync_reward_per_share(pool);
+
+    emit!(WithdrawEvent {
+        header: EventHeader {
+            master_chef: master_chef_loader.key(),
+            signer: Some(owner.key()),
+        },
+        lp_token,
+        amount,
+        user_amount: position_info.amount,
+        settled_reward: pending,
+        accrued_reward: position_info.accrued_reward,
+        lp_supply: pool.lp_supply,
+        acc_reward_per_share: pool.acc_reward_per_share,
+        slot: Clock::get()?.slot,
+        position: Some(position_mint.key()),
+    });
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! Account layouts of earlier program versions, kept so `migrate` can read them.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ACC_REWARD_PRECISION,
+    state::{PoolInfo, UserInfo, MAX_POOLS},
+};
+
+/// `I80F48` fixed point number (48 fractional bits) used by version 1 and earlier for the
+/// reward accumulator and the per user reward fields.
+#[zero_copy(unsafe)]
+#[repr(C)]
+#[derive(Default, Debug, AnchorDeserialize, AnchorSerialize)]
+pub struct WrappedI80F48 {
+    pub value: i128,
+}
+
+const FRACTION_BITS: u32 = 48;
+
+/// Converts the raw bits of a non-negative `I80F48` to the `ACC_REWARD_PRECISION` scale,
+/// rounding the fraction down or up. `None` on negative or overflowing values.
+pub fn fixed_to_scaled(bits: i128, round_up: bool) -> Option<u128> {
+    let bits = u128::try_from(bits).ok()?;
+    let whole = (bits >> FRACTION_BITS).checked_mul(ACC_REWARD_PRECISION)?;
+    // below 2^48 * 1e18, far from overflowing
+    let fraction = (bits & ((1 << FRACTION_BITS) - 1)) * ACC_REWARD_PRECISION;
+    let mut scaled_fraction = fraction >> FRACTION_BITS;
+    if round_up && fraction & ((1 << FRACTION_BITS) - 1) != 0 {
+        scaled_fraction += 1;
+    }
+    whole.checked_add(scaled_fraction)
+}
+
+/// Rewrites the fields version 1 and earlier kept as `I80F48` bits in a `UserInfo`: a
+/// reward debt of `amount * acc_reward_per_share` where `reward_per_share_paid` is now,
+/// and the accrued reward. Both round against the user, the snapshot never past the
+/// pool's converted `acc_reward_per_share`.
+pub fn migrate_fixed_user_info(user_info: &mut UserInfo, acc_reward_per_share: u128) -> Option<()> {
+    let reward_debt = user_info.reward_per_share_paid;
+    let amount = u128::from(user_info.amount);
+    user_info.reward_per_share_paid = match reward_debt.checked_div(amount) {
+        None => acc_reward_per_share,
+        Some(quotient) => {
+            let per_share = if quotient * amount == reward_debt {
+                quotient
+            } else {
+                quotient + 1
+            };
+            fixed_to_scaled(i128::try_from(per_share).ok()?, true)?.min(acc_reward_per_share)
+        }
+    };
+    user_info.accrued_reward =
+        fixed_to_scaled(i128::try_from(user_info.accrued_reward).ok()?, false)?;
+    Some(())
+}
+
+#[zero_copy(unsafe)]
+#[repr(C)]
+pub struct PoolInfoV0 {
+    pub reward_token: Pubkey,
+    pub lp_token: Pubkey,
+    pub lp_supply: u64,
+    pub start_slot: u64,
+    pub reward_per_slot: u64,
+    pub last_reward_slot: u64,
+    pub acc_reward_per_share: WrappedI80F48,
+    pub initialized: bool,
+
+    pub lp_token_vault: Pubkey,
+    pub lp_token_vault_bump: u8,
+    pub lp_token_vault_authority_bump: u8,
+
+    pub reward_token_vault: Pubkey,
+    pub reward_token_vault_bump: u8,
+    pub reward_token_vault_authority_bump: u8,
+}
+
+// Same guarantees `#[account(zero_copy(unsafe))]` gives `MasterChef`: every field is plain data.
+unsafe impl bytemuck::Zeroable for PoolInfoV0 {}
+unsafe impl bytemuck::Pod for PoolInfoV0 {}
+
+impl PoolInfoV0 {
+    pub fn migrate_into(&self, pool: &mut PoolInfo) -> Option<()> {
+        pool.reward_token = self.reward_token;
+        pool.lp_token = self.lp_token;
+        pool.lp_supply = self.lp_supply;
+        pool.start_slot = self.start_slot;
+        pool.reward_per_slot = self.reward_per_slot;
+        pool.last_reward_slot = self.last_reward_slot;
+        pool.acc_reward_per_share = fixed_to_scaled(self.acc_reward_per_share.value, false)?;
+        pool.initialized = self.initialized;
+        pool.lp_token_vault = self.lp_token_vault;
+        pool.lp_token_vault_bump = self.lp_token_vault_bump;
+        pool.lp_token_vault_authority_bump = self.lp_token_vault_authority_bump;
+        pool.reward_token_vault = self.reward_token_vault;
+        pool.reward_token_vault_bump = self.reward_token_vault_bump;
+        pool.reward_token_vault_authority_bump = self.reward_token_vault_authority_bump;
+        Some(())
+    }
+}
+
+#[zero_copy(unsafe)]
+#[repr(C)]
+pub struct MasterChefV0 {
+    pub admin: Pubkey,
+    pub pools: [PoolInfoV0; MAX_POOLS],
+}
+
+impl MasterChefV0 {
+    pub const LEN: usize = std::mem::size_of::<MasterChefV0>();
+
+    /// `pools` is the trailing field, so it ends exactly where the struct does.
+    const POOLS_OFFSET: usize = Self::LEN - MAX_POOLS * std::mem::size_of::<PoolInfoV0>();
+
+    /// Reads pool `index` out of a raw (possibly unaligned) V0 account body.
+    pub fn read_pool(data: &[u8], index: usize) -> PoolInfoV0 {
+        let start = Self::POOLS_OFFSET + index * std::mem::size_of::<PoolInfoV0>();
+        bytemuck::pod_read_unaligned(&data[start..start + std::mem::size_of::<PoolInfoV0>()])
+    }
+}
@@ -1,7 +1,10 @@
+#![allow(clippy::result_large_err)]
+
 pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod legacy;
 pub mod macros;
 pub mod state;
 pub mod utils;
@@ -56,8 +59,9 @@ pub mod master_chef {
         ctx: Context<Deposit>,
         lp_token: Pubkey,
         amount: u64,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        instructions::deposit(ctx, lp_token, amount)
+        instructions::deposit(ctx, lp_token, amount, proof)
     }
 
     pub fn withdraw(
@@ -71,7 +75,145 @@ pub mod master_chef {
     pub fn claim_reward(ctx: Context<ClaimReward>, lp_token: Pubkey) ->Result<()> {
         instructions::claim_reward(ctx, lp_token)
     }
-}
 
+    pub fn migrate<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+        instructions::migrate(ctx)
+    }
+
+    pub fn migrate_user_info(ctx: Context<MigrateUserInfo>, lp_token: Pubkey) -> Result<()> {
+        instructions::migrate_user_info(ctx, lp_token)
+    }
+
+    pub fn migrate_position_info(
+        ctx: Context<MigratePositionInfo>,
+        lp_token: Pubkey,
+    ) -> Result<()> {
+        instructions::migrate_position_info(ctx, lp_token)
+    }
+
+    pub fn close_user_info(ctx: Context<CloseUserInfo>, lp_token: Pubkey) -> Result<()> {
+        instructions::close_user_info(ctx, lp_token)
+    }
+
+    pub fn close_pool(ctx: Context<ClosePool>, lp_token: Pubkey, grace_slots: u64) -> Result<()> {
+        instructions::close_pool(ctx, lp_token, grace_slots)
+    }
+
+    pub fn finalize_close_pool(ctx: Context<FinalizeClosePool>, lp_token: Pubkey) -> Result<()> {
+        instructions::finalize_close_pool(ctx, lp_token)
+    }
+
+    pub fn withdraw_excess_rewards(
+        ctx: Context<WithdrawExcessRewards>,
+        lp_token: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_excess_rewards(ctx, lp_token, amount)
+    }
+
+    pub fn rescue_tokens(
+        ctx: Context<RescueTokens>,
+        lp_token: Pubkey,
+        vault_type: PoolVaultType,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::rescue_tokens(ctx, lp_token, vault_type, amount)
+    }
+
+    pub fn configure_pool(ctx: Context<ConfigurePool>, lp_token: Pubkey, config: PoolConfig) -> Result<()> {
+        instructions::configure_pool(ctx, lp_token, config)
+    }
+
+    pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, lp_token: Pubkey, user: Pubkey) -> Result<()> {
+        instructions::add_to_allowlist(ctx, lp_token, user)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>, lp_token: Pubkey, user: Pubkey) -> Result<()> {
+        instructions::remove_from_allowlist(ctx, lp_token, user)
+    }
+
+    pub fn request_withdraw(ctx: Context<RequestWithdraw>, lp_token: Pubkey, amount: u64) -> Result<()> {
+        instructions::request_withdraw(ctx, lp_token, amount)
+    }
+
+    pub fn complete_withdraw(ctx: Context<CompleteWithdraw>, lp_token: Pubkey) -> Result<()> {
+        instructions::complete_withdraw(ctx, lp_token)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, lp_token: Pubkey) -> Result<()> {
+        instructions::register_referrer(ctx, lp_token)
+    }
+
+    pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>, lp_token: Pubkey) -> Result<()> {
+        instructions::claim_referral_reward(ctx, lp_token)
+    }
+
+    pub fn set_emission_schedule(
+        ctx: Context<SetEmissionSchedule>,
+        lp_token: Pubkey,
+        segments: Vec<EmissionSegment>,
+        halving_period: u64,
+    ) -> Result<()> {
+        instructions::set_emission_schedule(ctx, lp_token, segments, halving_period)
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>, lp_token: Pubkey) -> Result<()> {
+        instructions::open_position(ctx, lp_token)
+    }
+
+    pub fn deposit_position(
+        ctx: Context<DepositPosition>,
+        lp_token: Pubkey,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::deposit_position(ctx, lp_token, amount, proof)
+    }
+
+    pub fn withdraw_position(ctx: Context<WithdrawPosition>, lp_token: Pubkey, amount: u64) -> Result<()> {
+        instructions::withdraw_position(ctx, lp_token, amount)
+    }
+
+    pub fn claim_position_reward(ctx: Context<ClaimPositionReward>, lp_token: Pubkey) -> Result<()> {
+        instructions::claim_position_reward(ctx, lp_token)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>, lp_token: Pubkey) -> Result<()> {
+        instructions::close_position(ctx, lp_token)
+    }
+
+    pub fn transfer_position(
+        ctx: Context<TransferPosition>,
+        lp_token: Pubkey,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::transfer_position(ctx, lp_token, amount, proof)
+    }
+
+    pub fn create_receipt_mint(ctx: Context<CreateReceiptMint>, lp_token: Pubkey) -> Result<()> {
+        instructions::create_receipt_mint(ctx, lp_token)
+    }
+
+    pub fn claim_all<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAll<'info>>, lp_tokens: Vec<Pubkey>) -> Result<()> {
+        instructions::claim_all(ctx, lp_tokens)
+    }
+
+    pub fn deposit_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositMany<'info>>,
+        lp_tokens: Vec<Pubkey>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::deposit_many(ctx, lp_tokens, amounts)
+    }
+
+    pub fn sweep_reward_dust(ctx: Context<SweepRewardDust>, lp_token: Pubkey) -> Result<()> {
+        instructions::sweep_reward_dust(ctx, lp_token)
+    }
+
+    pub fn audit_pool(ctx: Context<AuditPool>, lp_token: Pubkey) -> Result<()> {
+        instructions::audit_pool(ctx, lp_token)
+    }
+}
 
 
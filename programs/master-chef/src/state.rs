@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
-use fixed::types::I80F48;
-use std::fmt::{Debug, Formatter};
 use crate::constants::*;
 use crate::{check, math_error, set_if_some};
 use crate::errors::MasterChefError;
+use crate::events::{EventHeader, PoolUpdatedEvent};
 use solana_program::clock::Clock;
+use solana_program::keccak;
 use anchor_spl::token::{transfer, Transfer};
 
 
@@ -14,13 +14,148 @@ use anchor_spl::token::{transfer, Transfer};
 #[derive(Default)]
 pub struct UserInfo {
     pub amount: u64,
-    pub reward_debt: WrappedI80F48,
-    pub accrued_reward: WrappedI80F48,
+    /// `acc_reward_per_share` the staked amount was last settled at.
+    pub reward_per_share_paid: u128,
+    /// Settled rewards not claimed yet, scaled by `ACC_REWARD_PRECISION`.
+    pub accrued_reward: u128,
+    pub version: u8,
+    /// Amount waiting for `complete_withdraw`, not earning rewards.
+    pub cooling_amount: u64,
+    /// Slot from which `cooling_amount` can be withdrawn.
+    pub cooldown_end_slot: u64,
+    /// Referrer recorded on the first deposit that named one, default when none.
+    pub referrer: Pubkey,
+    pub reserved: [u64; 10],
+}
+
+impl UserInfo {
+    pub const LEN: usize = 8 + 8 + 16 + 16 + 1 + 8 + 8 + 32 + 8 * 10;
+
+    pub fn has_referrer(&self) -> bool {
+        self.referrer != Pubkey::default()
+    }
+
+    /// Settled rewards add up to at least one base unit.
+    pub fn has_claimable_reward(&self) -> bool {
+        self.accrued_reward >= ACC_REWARD_PRECISION
+    }
+
+    /// Nothing staked, cooling or left to claim. A fraction of a base unit may still be
+    /// accrued, it goes to the pool's `reward_dust` when the account is closed. Accounts not
+    /// migrated yet keep `I80F48` bits in `accrued_reward`, where any non-zero value is owed.
+    pub fn is_empty(&self) -> bool {
+        let has_reward = if self.version == ACCOUNT_VERSION {
+            self.has_claimable_reward()
+        } else {
+            self.accrued_reward != 0
+        };
+        self.amount == 0 && self.cooling_amount == 0 && !has_reward
+    }
+
+    /// Stamps an account holding nothing with `ACCOUNT_VERSION`, so new accounts need no
+    /// migration, and rejects any other account that has not been migrated yet.
+    pub fn check_version(&mut self) -> Result<()> {
+        if self.version != ACCOUNT_VERSION && self.is_empty() {
+            self.version = ACCOUNT_VERSION;
+        }
+        check!(self.version == ACCOUNT_VERSION, MasterChefError::AccountNotMigrated);
+        Ok(())
+    }
+
+    /// Moves the reward pending on the staked amount into `accrued_reward` and returns it,
+    /// scaled by `ACC_REWARD_PRECISION`. `reward_per_share_paid` must be synced once the
+    /// staked amount is final.
+    ///
+    /// The staked amount was part of `lp_supply` whenever rewards were added to the pool
+    /// since the last sync, so the pending reward never exceeds what was emitted in between
+    /// and the product cannot overflow before the pool accumulator does.
+    pub fn settle(&mut self, pool: &PoolInfo) -> Result<u128> {
+        check!(self.version == ACCOUNT_VERSION, MasterChefError::AccountNotMigrated);
+        let pending = pool.acc_reward_per_share
+            .checked_sub(self.reward_per_share_paid)
+            .ok_or_else(math_error!())?
+            .checked_mul(u128::from(self.amount))
+            .ok_or_else(math_error!())?;
+        self.accrued_reward = self.accrued_reward.checked_add(pending).ok_or_else(math_error!())?;
+        Ok(pending)
+    }
+
+    pub fn sync_reward_per_share(&mut self, pool: &PoolInfo) {
+        self.reward_per_share_paid = pool.acc_reward_per_share;
+    }
+
+    /// Takes the whole tokens out of `accrued_reward` and returns them. The fraction of a
+    /// base unit stays for the next claim.
+    pub fn take_accrued_reward(&mut self) -> Result<u64> {
+        let reward_amount = u64::try_from(self.accrued_reward / ACC_REWARD_PRECISION)
+            .ok()
+            .ok_or_else(math_error!())?;
+        self.accrued_reward %= ACC_REWARD_PRECISION;
+        Ok(reward_amount)
+    }
+}
+
+/// Referral earnings of a referrer in a pool.
+#[account]
+#[derive(Default)]
+pub struct ReferrerInfo {
+    pub referrer: Pubkey,
+    /// Number of users that recorded this referrer.
+    pub referee_count: u64,
+    /// Referral rewards not claimed yet.
+    pub accrued_reward: u64,
+    /// Referral rewards earned over the lifetime of the account.
+    pub total_reward: u64,
+    pub bump: u8,
+}
+
+impl ReferrerInfo {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+/// Grants a user access to an allowlisted pool, written by the admin.
+#[account]
+#[derive(Default)]
+pub struct AllowlistEntry {
+    pub bump: u8,
+}
+
+impl AllowlistEntry {
+    pub const LEN: usize = 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolGateMode {
+    /// Anyone can deposit.
+    Open,
+    /// Depositors need an `AllowlistEntry` for the pool.
+    Allowlist,
+    /// Depositors need a Merkle proof against `PoolInfo::merkle_root`.
+    Merkle,
+}
+
+impl From<PoolGateMode> for u8 {
+    fn from(mode: PoolGateMode) -> Self {
+        mode as u8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolRewardMode {
+    /// Rewards are paid out of the pre-funded reward token vault.
+    Vault,
+    /// Rewards are minted by the master chef mint authority PDA.
+    Mint,
+}
+
+impl From<PoolRewardMode> for u8 {
+    fn from(mode: PoolRewardMode) -> Self {
+        mode as u8
+    }
 }
 
 #[zero_copy(unsafe)]
 #[repr(C)]
-#[derive(Default)]
 pub struct PoolInfo {
     pub reward_token: Pubkey,
     pub lp_token: Pubkey,
@@ -28,7 +163,8 @@ pub struct PoolInfo {
     pub start_slot: u64,
     pub reward_per_slot: u64,
     pub last_reward_slot: u64,
-    pub acc_reward_per_share: WrappedI80F48,
+    /// Rewards per staked LP base unit since the pool started, scaled by `ACC_REWARD_PRECISION`.
+    pub acc_reward_per_share: u128,
     // bool值放第一位时，客户端fetch数据时报错：invalid bool？
     pub initialized: bool,
 
@@ -39,26 +175,354 @@ pub struct PoolInfo {
     pub reward_token_vault: Pubkey,
     pub reward_token_vault_bump: u8,
     pub reward_token_vault_authority_bump: u8,
+
+    pub version: u8,
+    /// Slot from which a closing pool can be finalized, 0 while the pool is open.
+    pub close_slot: u64,
+    /// Rewards emitted to stakers that have not been claimed yet, in whole tokens.
+    pub reward_owed: u64,
+    /// Upper bound of `lp_supply`, 0 when uncapped.
+    pub max_total_deposit: u64,
+    /// Upper bound of a single user's staked amount, 0 when uncapped.
+    pub max_deposit_per_user: u64,
+    /// `PoolGateMode` of the pool.
+    pub gate_mode: u8,
+    /// Root of the depositor Merkle tree, used in `PoolGateMode::Merkle`.
+    pub merkle_root: [u8; 32],
+    /// Slots between `request_withdraw` and `complete_withdraw`, 0 allows direct withdrawals.
+    pub unbonding_slots: u64,
+    /// LP tokens still in the vault but requested for withdrawal, excluded from `lp_supply`.
+    pub cooling_supply: u64,
+    /// Referral rewards credited to referrers and not claimed yet. They are paid on top of
+    /// emissions, so they are kept out of `reward_owed`.
+    pub referral_owed: u64,
+    /// Share of every claim credited to the claimer's referrer, in basis points.
+    pub referral_bps: u16,
+    /// `PoolRewardMode` of the pool.
+    pub reward_mode: u8,
+    /// Slots after which the emission rate halves, counted from `start_slot`. 0 disables halvings.
+    pub halving_period: u64,
+    /// Rate changes taking effect at their start slot, sorted by it. Only the first
+    /// `segment_count` entries are used, before the first one `reward_per_slot` applies.
+    pub segments: [EmissionSegment; MAX_SEGMENTS],
+    pub segment_count: u8,
+    /// Emissions before this slot are multiplied by `bonus_multiplier`.
+    pub bonus_end_slot: u64,
+    pub bonus_multiplier: u64,
+    /// Mint of the receipt tokens minted 1:1 on deposit, default when the pool has none.
+    pub receipt_mint: Pubkey,
+    /// Whole tokens of `reward_dust` released by `sweep_reward_dust` so far.
+    pub swept_reward_dust: u64,
+    /// Emitted rewards not spread over `lp_supply` yet because they round to less than one
+    /// unit of `acc_reward_per_share`, scaled by `ACC_REWARD_PRECISION`. Carried into the
+    /// next update.
+    pub reward_remainder: u128,
+    /// Fractions of a base unit left behind by closed accounts, scaled by
+    /// `ACC_REWARD_PRECISION`.
+    pub reward_dust: u128,
+    pub reserved: [u8; 168],
 }
 
 impl PoolInfo {
 
-    pub fn update_pool(&mut self) -> Result<()> {
+    pub fn update_pool(&mut self, master_chef: Pubkey) -> Result<()> {
         let current_slot = Clock::get()?.slot;
         if current_slot <= self.last_reward_slot {
             return Ok(())
         }
+        let last_reward_slot = self.last_reward_slot;
+        let mut reward_amount = 0;
+        if self.lp_supply > 0 {
+            reward_amount = self.rewards_between(self.last_reward_slot, current_slot)?;
+            self.accrue_reward(reward_amount)?;
+            self.reward_owed = self.reward_owed.checked_add(reward_amount).ok_or_else(math_error!())?;
+        }
+        self.last_reward_slot = current_slot;
+
+        emit!(PoolUpdatedEvent {
+            header: EventHeader {
+                master_chef,
+                signer: None,
+            },
+            lp_token: self.lp_token,
+            slot: current_slot,
+            last_reward_slot,
+            reward_amount,
+            lp_supply: self.lp_supply,
+            acc_reward_per_share: self.acc_reward_per_share,
+        });
+        Ok(())
+    }
+
+    /// Spreads `reward_amount` over the staked `lp_supply`, rounding down so stakers are
+    /// never credited more than was emitted. What the division leaves over is kept in
+    /// `reward_remainder` and added to the next call, so nothing emitted is lost.
+    ///
+    /// A call adds at most `u64::MAX * ACC_REWARD_PRECISION` (with a single staked unit),
+    /// about 1.8e37 of the 3.4e38 a u128 holds, so the accumulator overflows only once
+    /// more than 3.4e20 reward units have been emitted per staked base unit, beyond any
+    /// u64 token supply. `UserInfo::settle` is bounded by the same figure.
+    pub fn accrue_reward(&mut self, reward_amount: u64) -> Result<()> {
         if self.lp_supply == 0 {
-            self.last_reward_slot = current_slot;
             return Ok(());
         }
-        let slot_delta = current_slot - self.last_reward_slot;
-        let reward_amount = slot_delta.checked_mul(self.reward_per_slot).ok_or_else(math_error!())?;
-        self.acc_reward_per_share = I80F48::from_num(reward_amount)
-            .checked_div(I80F48::from_num(self.lp_supply))
+        let scaled_reward = u128::from(reward_amount)
+            .checked_mul(ACC_REWARD_PRECISION)
             .ok_or_else(math_error!())?
-            .into();
-        self.last_reward_slot = current_slot;
+            .checked_add(self.reward_remainder)
+            .ok_or_else(math_error!())?;
+        let lp_supply = u128::from(self.lp_supply);
+        self.acc_reward_per_share = self
+            .acc_reward_per_share
+            .checked_add(scaled_reward / lp_supply)
+            .ok_or_else(math_error!())?;
+        self.reward_remainder = scaled_reward % lp_supply;
+        Ok(())
+    }
+
+    /// Adds the unclaimable fraction of a closed account to `reward_dust`.
+    pub fn forfeit_reward_dust(&mut self, accrued_reward: u128) -> Result<()> {
+        self.reward_dust = self.reward_dust.checked_add(accrued_reward).ok_or_else(math_error!())?;
+        Ok(())
+    }
+
+    /// Takes the whole tokens out of `reward_dust` and returns them.
+    pub fn take_reward_dust(&mut self) -> Result<u64> {
+        let amount = u64::try_from(self.reward_dust / ACC_REWARD_PRECISION)
+            .ok()
+            .ok_or_else(math_error!())?;
+        self.reward_dust %= ACC_REWARD_PRECISION;
+        self.swept_reward_dust = self.swept_reward_dust.checked_add(amount).ok_or_else(math_error!())?;
+        Ok(amount)
+    }
+
+    /// Once nothing is staked anymore the remainder can never be spread, so it joins
+    /// `reward_dust` and the whole tokens of the dust are released from `reward_owed`.
+    /// Returns the released amount.
+    pub fn release_undistributed_reward(&mut self) -> Result<u64> {
+        check!(
+            self.lp_supply == 0,
+            MasterChefError::PoolNotEmpty
+        );
+        let remainder = std::mem::take(&mut self.reward_remainder);
+        self.forfeit_reward_dust(remainder)?;
+        let amount = self.take_reward_dust()?;
+        self.reward_owed = self.reward_owed.checked_sub(amount).ok_or_else(math_error!())?;
+        Ok(amount)
+    }
+
+    /// Once `CLAIM_DEADLINE_SLOTS` have passed since `close_slot`, drops what stakers and
+    /// referrers still have not claimed and returns it, so a closing pool cannot keep its
+    /// slot forever. Returns 0 before the deadline.
+    pub fn forfeit_unclaimed_reward(&mut self, slot: u64) -> Result<u64> {
+        let deadline = self.close_slot.checked_add(CLAIM_DEADLINE_SLOTS).ok_or_else(math_error!())?;
+        if !self.is_closing() || slot < deadline {
+            return Ok(0);
+        }
+        let forfeited = self.total_owed()?;
+        self.reward_owed = 0;
+        self.referral_owed = 0;
+        Ok(forfeited)
+    }
+
+    /// Everything the reward vault has to keep for stakers and referrers.
+    pub fn total_owed(&self) -> Result<u64> {
+        Ok(self.reward_owed.checked_add(self.referral_owed).ok_or_else(math_error!())?)
+    }
+
+    /// Part of a reward vault balance of `vault_amount` that nobody is owed. Mint mode pools
+    /// mint what they owe, so their whole vault is excess.
+    pub fn excess_reward(&self, vault_amount: u64) -> Result<u64> {
+        if self.is_mint_mode() {
+            return Ok(vault_amount);
+        }
+        Ok(vault_amount.saturating_sub(self.total_owed()?))
+    }
+
+    pub fn is_closing(&self) -> bool {
+        self.close_slot != 0
+    }
+
+    /// Emission rate at `slot`: the last segment started by then (or `reward_per_slot`),
+    /// halved once per elapsed `halving_period`. Closing pools emit nothing.
+    pub fn reward_per_slot_at(&self, slot: u64) -> u64 {
+        if self.is_closing() {
+            return 0;
+        }
+        let rate = self.segments[..self.segment_count as usize]
+            .iter()
+            .rev()
+            .find(|segment| segment.start_slot <= slot)
+            .map_or(self.reward_per_slot, |segment| segment.reward_per_slot);
+        match self.halvings_at(slot) {
+            halvings if halvings >= u64::BITS as u64 => 0,
+            halvings => rate >> halvings,
+        }
+    }
+
+    fn halvings_at(&self, slot: u64) -> u64 {
+        if self.halving_period == 0 || slot < self.start_slot {
+            return 0;
+        }
+        (slot - self.start_slot) / self.halving_period
+    }
+
+    /// First slot after `slot` at which the emission rate may change.
+    fn next_rate_change(&self, slot: u64) -> Option<u64> {
+        let segment_start = self.segments[..self.segment_count as usize]
+            .iter()
+            .map(|segment| segment.start_slot)
+            .find(|start_slot| *start_slot > slot);
+        // Once the rate has been halved to zero further halvings change nothing
+        let halving = if self.halving_period == 0 || self.halvings_at(slot) >= u64::BITS as u64 {
+            None
+        } else if slot < self.start_slot {
+            self.start_slot.checked_add(self.halving_period)
+        } else {
+            (self.halvings_at(slot) + 1)
+                .checked_mul(self.halving_period)
+                .and_then(|elapsed| self.start_slot.checked_add(elapsed))
+        };
+        match (segment_start, halving) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Number of reward slots in `[from, to)`, slots before `bonus_end_slot` counting `bonus_multiplier` times,
+    /// zero for an empty range.
+    /// Pools created before bonuses existed store a zero multiplier, which counts as 1.
+    pub fn get_multiplier(&self, from: u64, to: u64) -> Result<u64> {
+        if from >= to {
+            return Ok(0);
+        }
+        let bonus_slots = self.bonus_end_slot.clamp(from, to) - from;
+        Ok(bonus_slots
+            .checked_mul(self.bonus_multiplier.max(1))
+            .ok_or_else(math_error!())?
+            .checked_add(to - from - bonus_slots)
+            .ok_or_else(math_error!())?)
+    }
+
+    /// Rewards emitted over the slots in `[from, to)`, integrated across rate changes.
+    pub fn rewards_between(&self, from: u64, to: u64) -> Result<u64> {
+        let mut reward_amount: u64 = 0;
+        let mut slot = from;
+        while slot < to {
+            let next = self.next_rate_change(slot).map_or(to, |next| next.min(to));
+            let emitted = self.get_multiplier(slot, next)?.checked_mul(self.reward_per_slot_at(slot)).ok_or_else(math_error!())?;
+            reward_amount = reward_amount.checked_add(emitted).ok_or_else(math_error!())?;
+            slot = next;
+        }
+        Ok(reward_amount)
+    }
+
+    /// Replaces the emission schedule, segments must be sorted by strictly increasing start slot.
+    pub fn set_emission_schedule(&mut self, segments: &[EmissionSegment], halving_period: u64) -> Result<()> {
+        check!(
+            segments.len() <= MAX_SEGMENTS,
+            MasterChefError::TooManySegments
+        );
+        check!(
+            segments.windows(2).all(|pair| pair[0].start_slot < pair[1].start_slot),
+            MasterChefError::UnsortedSegments
+        );
+        self.segments = [EmissionSegment::default(); MAX_SEGMENTS];
+        self.segments[..segments.len()].copy_from_slice(segments);
+        self.segment_count = segments.len() as u8;
+        self.halving_period = halving_period;
+        Ok(())
+    }
+
+    pub fn is_mint_mode(&self) -> bool {
+        self.reward_mode == u8::from(PoolRewardMode::Mint)
+    }
+
+    pub fn has_receipt_mint(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
+    }
+
+    pub fn configure(&mut self, config: &PoolConfig) -> Result<()> {
+        set_if_some!(self.max_total_deposit, config.max_total_deposit);
+        set_if_some!(self.max_deposit_per_user, config.max_deposit_per_user);
+        set_if_some!(self.gate_mode, config.gate_mode);
+        set_if_some!(self.merkle_root, config.merkle_root);
+        set_if_some!(self.unbonding_slots, config.unbonding_slots);
+        if let Some(referral_bps) = config.referral_bps {
+            check!(
+                u64::from(referral_bps) <= BPS_DENOMINATOR,
+                MasterChefError::InvalidReferralRate
+            );
+        }
+        set_if_some!(self.referral_bps, config.referral_bps);
+        set_if_some!(self.reward_mode, config.reward_mode);
+        if let Some(bonus_multiplier) = config.bonus_multiplier {
+            check!(
+                bonus_multiplier >= 1,
+                MasterChefError::InvalidBonusMultiplier
+            );
+        }
+        set_if_some!(self.bonus_end_slot, config.bonus_end_slot);
+        set_if_some!(self.bonus_multiplier, config.bonus_multiplier);
+        Ok(())
+    }
+
+    /// Checks that `user` may deposit into the pool, either through an allowlist entry or a
+    /// Merkle proof depending on the gate mode. Leaves are `keccak(user)` and pairs are hashed
+    /// sorted, so proofs need no position bits.
+    pub fn check_gate(&self, user: &Pubkey, allowlisted: bool, proof: &[[u8; 32]]) -> Result<()> {
+        if self.gate_mode == u8::from(PoolGateMode::Allowlist) {
+            check!(allowlisted, MasterChefError::NotAllowlisted);
+        } else if self.gate_mode == u8::from(PoolGateMode::Merkle) {
+            let root = proof.iter().fold(keccak::hash(user.as_ref()).to_bytes(), |node, sibling| {
+                if node <= *sibling {
+                    keccak::hashv(&[&node, sibling]).to_bytes()
+                } else {
+                    keccak::hashv(&[sibling, &node]).to_bytes()
+                }
+            });
+            check!(root == self.merkle_root, MasterChefError::InvalidMerkleProof);
+        }
+        Ok(())
+    }
+
+    /// Checks that staking `amount` more keeps both the pool and the user under their caps.
+    pub fn check_deposit_caps(&self, user_amount: u64, amount: u64) -> Result<()> {
+        let lp_supply = self.lp_supply.checked_add(amount).ok_or_else(math_error!())?;
+        check!(
+            self.max_total_deposit == 0 || lp_supply <= self.max_total_deposit,
+            MasterChefError::PoolDepositCapExceeded,
+            "lp supply {} exceeds cap {}",
+            lp_supply,
+            self.max_total_deposit
+        );
+        self.check_user_deposit_cap(user_amount, amount)
+    }
+
+    pub fn check_user_deposit_cap(&self, user_amount: u64, amount: u64) -> Result<()> {
+        let user_amount = user_amount.checked_add(amount).ok_or_else(math_error!())?;
+        check!(
+            self.max_deposit_per_user == 0 || user_amount <= self.max_deposit_per_user,
+            MasterChefError::UserDepositCapExceeded,
+            "user amount {} exceeds cap {}",
+            user_amount,
+            self.max_deposit_per_user
+        );
+        Ok(())
+    }
+
+    /// Checks `vault` is the pool's vault of `vault_type`. Vault authorities need no check
+    /// of their own, transfers out of a vault are signed with `pool_signer!` and fail for
+    /// any other authority.
+    pub fn check_vault(&self, vault_type: PoolVaultType, vault: &Pubkey) -> Result<()> {
+        let expected = match vault_type {
+            PoolVaultType::LPTokenVault => self.lp_token_vault,
+            PoolVaultType::RewardTokenVault => self.reward_token_vault,
+        };
+        check!(
+            *vault == expected,
+            MasterChefError::InvalidPoolVault
+        );
         Ok(())
     }
 
@@ -73,14 +537,6 @@ impl PoolInfo {
             MasterChefError::InvalidTransfer
         );
 
-        msg!(
-            "deposit_spl_transfer: amount: {} from {} to {}, auth {}",
-            amount,
-            accounts.from.key,
-            accounts.to.key,
-            accounts.authority.key
-        );
-
         transfer(CpiContext::new(program, accounts), amount)
     }
 
@@ -91,14 +547,6 @@ impl PoolInfo {
         program: AccountInfo<'c>,
         signer_seeds: &[&[&[u8]]],
     ) -> Result<()> {
-        msg!(
-            "withdraw_spl_transfer: amount: {} from {} to {}, auth {}",
-            amount,
-            accounts.from.key,
-            accounts.to.key,
-            accounts.authority.key
-        );
-
         transfer(
             CpiContext::new_with_signer(program, accounts, signer_seeds),
             amount,
@@ -108,30 +556,67 @@ impl PoolInfo {
 
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Debug, Clone)]
 pub struct PoolConfig {
-    reward_token: Option<Pubkey>,
-    lp_token: Option<Pubkey>,
-    start_slot: Option<Pubkey>,
-    reward_per_slot: Option<Pubkey>,
+    pub max_total_deposit: Option<u64>,
+    pub max_deposit_per_user: Option<u64>,
+    pub gate_mode: Option<PoolGateMode>,
+    pub merkle_root: Option<[u8; 32]>,
+    pub unbonding_slots: Option<u64>,
+    pub referral_bps: Option<u16>,
+    pub reward_mode: Option<PoolRewardMode>,
+    pub bonus_end_slot: Option<u64>,
+    pub bonus_multiplier: Option<u64>,
 }
 
-const MAX_POOLS: usize = 8;
+pub const MAX_POOLS: usize = 8;
+
+pub const MAX_SEGMENTS: usize = 8;
+
+/// Pools handled by one `claim_all` or `deposit_many`, keeps a batch within the default
+/// 200k compute unit budget of a transaction.
+pub const MAX_BATCH_POOLS: usize = 4;
+
+#[zero_copy(unsafe)]
+#[repr(C)]
+#[derive(Default, Debug, AnchorDeserialize, AnchorSerialize)]
+pub struct EmissionSegment {
+    pub start_slot: u64,
+    pub reward_per_slot: u64,
+}
 
 #[account(zero_copy(unsafe))]
 #[repr(C)]
 pub struct MasterChef {
     pub admin: Pubkey,
     pub pools: [PoolInfo; MAX_POOLS],
+    pub version: u8,
+    /// Share of minted rewards minted to `dev_fund` on top, in basis points.
+    pub dev_fund_bps: u16,
+    /// Owner of the token account receiving the dev fund share.
+    pub dev_fund: Pubkey,
+    /// Reward mint supply that mint mode pools never exceed, 0 when uncapped.
+    pub max_reward_supply: u64,
+    pub reserved: [u8; 209],
 }
 
 impl MasterChef {
     
     pub fn configure(&mut self, config: &MasterChefConfig) -> Result<()> {
         set_if_some!(self.admin, config.admin);
+        set_if_some!(self.dev_fund, config.dev_fund);
+        if let Some(dev_fund_bps) = config.dev_fund_bps {
+            check!(
+                u64::from(dev_fund_bps) <= BPS_DENOMINATOR,
+                MasterChefError::InvalidDevFundRate
+            );
+        }
+        set_if_some!(self.dev_fund_bps, config.dev_fund_bps);
+        set_if_some!(self.max_reward_supply, config.max_reward_supply);
         Ok(())
     }
 
     pub fn set_initial_configuration(&mut self, admin_pk: Pubkey) {
         self.admin = admin_pk;
+        self.version = ACCOUNT_VERSION;
     }
 
     pub fn get_first_empty_pool(&self) -> Option<usize> {
@@ -139,14 +624,16 @@ impl MasterChef {
     }
 
     pub fn find_pool(&mut self, lp_token: &Pubkey) -> Result<&mut PoolInfo> {
+        check!(self.version == ACCOUNT_VERSION, MasterChefError::AccountNotMigrated);
         let pool = self.pools
         .iter_mut()
         .find(|pool| pool.initialized && pool.lp_token.eq(lp_token))
-        .ok_or_else(|| MasterChefError::PoolNotFind)?;
+        .ok_or(MasterChefError::PoolNotFind)?;
 
         Ok(pool)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_pool(
         &mut self, 
         reward_token: Pubkey, 
@@ -176,13 +663,35 @@ impl MasterChef {
             start_slot,
             reward_per_slot,
             last_reward_slot: if start_slot < current_slot { current_slot } else { start_slot } ,
-            acc_reward_per_share: WrappedI80F48 { value: 0},
+            acc_reward_per_share: 0,
             lp_token_vault,
             lp_token_vault_bump,
             lp_token_vault_authority_bump,
             reward_token_vault,
             reward_token_vault_bump,
             reward_token_vault_authority_bump,
+            version: ACCOUNT_VERSION,
+            close_slot: 0,
+            reward_owed: 0,
+            max_total_deposit: 0,
+            max_deposit_per_user: 0,
+            gate_mode: PoolGateMode::Open.into(),
+            merkle_root: [0; 32],
+            unbonding_slots: 0,
+            cooling_supply: 0,
+            referral_owed: 0,
+            referral_bps: 0,
+            reward_mode: PoolRewardMode::Vault.into(),
+            halving_period: 0,
+            segments: [EmissionSegment::default(); MAX_SEGMENTS],
+            segment_count: 0,
+            bonus_end_slot: 0,
+            bonus_multiplier: 1,
+            receipt_mint: Pubkey::default(),
+            swept_reward_dust: 0,
+            reward_remainder: 0,
+            reward_dust: 0,
+            reserved: [0; 168],
         };
         Ok(&mut self.pools[empty_index])
     }
@@ -192,34 +701,12 @@ impl MasterChef {
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Debug, Clone)]
 pub struct MasterChefConfig {
     pub admin: Option<Pubkey>,
+    pub dev_fund: Option<Pubkey>,
+    pub dev_fund_bps: Option<u16>,
+    pub max_reward_supply: Option<u64>,
 }
 
-#[zero_copy]
-#[repr(C)]
-#[derive(Default, AnchorDeserialize, AnchorSerialize)]
-pub struct  WrappedI80F48 {
-    pub value: i128,
-}
-
-impl Debug for WrappedI80F48 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", I80F48::from_bits(self.value))
-    }
-}
-
-impl From<I80F48> for WrappedI80F48 {
-    fn from(i: I80F48) -> Self {
-        Self { value: i.to_bits()}
-    }
-}
-
-impl From<WrappedI80F48> for I80F48 {
-    fn from(w: WrappedI80F48) -> Self {
-        Self::from_bits(w.value)
-    }
-}
-
-#[derive(Debug, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PoolVaultType {
     LPTokenVault,
     RewardTokenVault,
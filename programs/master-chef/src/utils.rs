@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{self, burn, mint_to, Burn, Mint, MintTo};
+
+use crate::{check, constants::*, errors::MasterChefError, events::{EventHeader, RewardMintedEvent}, math_error, pool_signer, MasterChef, PoolInfo, PoolVaultType, UserInfo};
+
+/// Reallocs `account` to `new_len`, topping up rent from `payer` when it grows
+/// and returning the rent surplus to `refund_to` when it shrinks.
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    refund_to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_exempt = Rent::get()?.minimum_balance(new_len);
+    let lamports = account.lamports();
+    if rent_exempt > lamports {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_exempt - lamports,
+        )?;
+    } else if lamports > rent_exempt {
+        **account.try_borrow_mut_lamports()? -= lamports - rent_exempt;
+        **refund_to.try_borrow_mut_lamports()? += lamports - rent_exempt;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Loads the `UserInfo` of `user` in a pool from an account passed through `remaining_accounts`,
+/// checking its address since none of the usual constraints apply there.
+pub fn load_user_info<'info>(
+    account: &AccountInfo<'info>,
+    user: &Pubkey,
+    lp_token: &Pubkey,
+    master_chef: &Pubkey,
+) -> Result<Account<'info, UserInfo>> {
+    let (address, _) = Pubkey::find_program_address(&[user.as_ref(), lp_token.as_ref(), master_chef.as_ref()], &crate::ID);
+    check!(
+        account.key() == address && account.is_writable,
+        MasterChefError::BatchAccountsMismatch
+    );
+    Account::try_from(account)
+}
+
+/// Hands the fraction of a base unit left in an account being closed to its pool's
+/// `reward_dust`. Accounts not migrated yet hold nothing in that unit, and the dust of a
+/// finalized pool is gone with the pool.
+pub fn forfeit_reward_dust(master_chef: &mut MasterChef, lp_token: &Pubkey, user_info: &UserInfo) -> Result<()> {
+    if user_info.version != ACCOUNT_VERSION || user_info.accrued_reward == 0 {
+        return Ok(());
+    }
+    match master_chef.pools.iter_mut().find(|pool| pool.initialized && pool.lp_token.eq(lp_token)) {
+        Some(pool) => pool.forfeit_reward_dust(user_info.accrued_reward),
+        None => Ok(()),
+    }
+}
+
+/// Accounts and settings used by mint mode pools to mint rewards.
+pub struct RewardMinter<'a, 'info> {
+    pub master_chef: Pubkey,
+    pub lp_token: Pubkey,
+    pub signer: Pubkey,
+    pub reward_mint: &'a Account<'info, Mint>,
+    pub mint_authority: AccountInfo<'info>,
+    pub mint_authority_bump: u8,
+    /// Only required when a dev fund share is minted
+    pub dev_fund_token_account: Option<AccountInfo<'info>>,
+    pub token_program: AccountInfo<'info>,
+    pub dev_fund_bps: u16,
+    pub max_reward_supply: u64,
+}
+
+impl<'a, 'info> RewardMinter<'a, 'info> {
+    /// Mints `amount` to `to` and the dev fund share alongside it, refusing to push the
+    /// reward mint supply past `max_reward_supply`.
+    pub fn mint(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        // dev_fund_bps is capped at BPS_DENOMINATOR so the result always fits back into a u64
+        let dev_fund_amount = (u128::from(amount) * u128::from(self.dev_fund_bps) / u128::from(BPS_DENOMINATOR)) as u64;
+        let supply = self.reward_mint.supply
+            .checked_add(amount)
+            .ok_or_else(math_error!())?
+            .checked_add(dev_fund_amount)
+            .ok_or_else(math_error!())?;
+        check!(
+            self.max_reward_supply == 0 || supply <= self.max_reward_supply,
+            MasterChefError::MaxRewardSupplyExceeded,
+            "supply {} exceeds max {}",
+            supply,
+            self.max_reward_supply
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            MINT_AUTHORITY_SEED.as_bytes(),
+            self.master_chef.as_ref(),
+            &[self.mint_authority_bump],
+        ]];
+        let recipient = to.key();
+        let dev_fund_mint = match dev_fund_amount {
+            0 => None,
+            _ => Some((
+                self.dev_fund_token_account.clone().ok_or(MasterChefError::MintAccountsMissing)?,
+                dev_fund_amount,
+            )),
+        };
+        for (to, amount) in std::iter::once((to, amount)).chain(dev_fund_mint) {
+            if amount > 0 {
+                mint_to(
+                    CpiContext::new_with_signer(
+                        self.token_program.clone(),
+                        MintTo {
+                            mint: self.reward_mint.to_account_info(),
+                            to,
+                            authority: self.mint_authority.clone(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        emit!(RewardMintedEvent {
+            header: EventHeader {
+                master_chef: self.master_chef,
+                signer: Some(self.signer),
+            },
+            lp_token: self.lp_token,
+            to: recipient,
+            amount,
+            dev_fund_amount,
+            supply,
+        });
+        Ok(())
+    }
+}
+
+/// Accounts and settings used to pay out the rewards of a pool, either from its reward
+/// vault or minted when the pool is in mint mode.
+pub struct RewardPayer<'a, 'info> {
+    pub master_chef: Pubkey,
+    pub lp_token: Pubkey,
+    pub signer: Pubkey,
+    pub reward_mint: &'a Account<'info, Mint>,
+    pub reward_token_vault: AccountInfo<'info>,
+    pub reward_token_vault_authority: AccountInfo<'info>,
+    pub mint_authority: Option<AccountInfo<'info>>,
+    pub mint_authority_bump: Option<u8>,
+    pub dev_fund_token_account: Option<AccountInfo<'info>>,
+    pub token_program: AccountInfo<'info>,
+    pub dev_fund_bps: u16,
+    pub max_reward_supply: u64,
+}
+
+impl<'a, 'info> RewardPayer<'a, 'info> {
+    pub fn pay(self, pool: &PoolInfo, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        check!(
+            self.reward_mint.key() == pool.reward_token,
+            MasterChefError::InvalidRewardMint
+        );
+        if !pool.is_mint_mode() {
+            pool.check_vault(PoolVaultType::RewardTokenVault, self.reward_token_vault.key)?;
+            return pool.withdraw_sql_transfer(
+                amount,
+                token::Transfer {
+                    from: self.reward_token_vault,
+                    to,
+                    authority: self.reward_token_vault_authority,
+                },
+                self.token_program,
+                pool_signer!(PoolVaultType::RewardTokenVault, self.lp_token, pool.reward_token_vault_authority_bump, self.master_chef),
+            );
+        }
+        let (Some(mint_authority), Some(mint_authority_bump)) = (self.mint_authority, self.mint_authority_bump) else {
+            return Err(MasterChefError::MintAccountsMissing.into());
+        };
+        RewardMinter {
+            master_chef: self.master_chef,
+            lp_token: self.lp_token,
+            signer: self.signer,
+            reward_mint: self.reward_mint,
+            mint_authority,
+            mint_authority_bump,
+            dev_fund_token_account: self.dev_fund_token_account,
+            token_program: self.token_program,
+            dev_fund_bps: self.dev_fund_bps,
+            max_reward_supply: self.max_reward_supply,
+        }.mint(to, amount)
+    }
+}
+
+/// Accounts used to mint and burn the receipt tokens of a pool. Every account is
+/// optional since pools without a receipt mint ignore them.
+pub struct ReceiptAccounts<'info> {
+    pub master_chef: Pubkey,
+    pub receipt_mint: Option<AccountInfo<'info>>,
+    pub receipt_token_account: Option<AccountInfo<'info>>,
+    pub mint_authority: Option<AccountInfo<'info>>,
+    pub mint_authority_bump: Option<u8>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> ReceiptAccounts<'info> {
+    fn accounts(&self, pool: &PoolInfo) -> Result<(AccountInfo<'info>, AccountInfo<'info>)> {
+        let (Some(receipt_mint), Some(receipt_token_account)) =
+            (self.receipt_mint.clone(), self.receipt_token_account.clone()) else {
+            return Err(MasterChefError::ReceiptAccountsMissing.into());
+        };
+        check!(
+            receipt_mint.key() == pool.receipt_mint,
+            MasterChefError::InvalidReceiptMint
+        );
+        Ok((receipt_mint, receipt_token_account))
+    }
+
+    /// Mints `amount` receipt tokens for LP tokens just deposited.
+    pub fn mint(&self, pool: &PoolInfo, amount: u64) -> Result<()> {
+        if !pool.has_receipt_mint() || amount == 0 {
+            return Ok(());
+        }
+        let (receipt_mint, receipt_token_account) = self.accounts(pool)?;
+        let (Some(mint_authority), Some(mint_authority_bump)) = (self.mint_authority.clone(), self.mint_authority_bump) else {
+            return Err(MasterChefError::ReceiptAccountsMissing.into());
+        };
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                MintTo {
+                    mint: receipt_mint,
+                    to: receipt_token_account,
+                    authority: mint_authority,
+                },
+                &[&[MINT_AUTHORITY_SEED.as_bytes(), self.master_chef.as_ref(), &[mint_authority_bump]]],
+            ),
+            amount,
+        )
+    }
+
+    /// Burns `amount` receipt tokens of `authority` for LP tokens leaving the pool.
+    pub fn burn(&self, pool: &PoolInfo, authority: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if !pool.has_receipt_mint() || amount == 0 {
+            return Ok(());
+        }
+        let (receipt_mint, receipt_token_account) = self.accounts(pool)?;
+        burn(
+            CpiContext::new(
+                self.token_program.clone(),
+                Burn {
+                    mint: receipt_mint,
+                    from: receipt_token_account,
+                    authority,
+                },
+            ),
+            amount,
+        )
+    }
+}
@@ -0,0 +1,374 @@
+use master_chef::{
+    constants::{ACCOUNT_VERSION, ACC_REWARD_PRECISION, CLAIM_DEADLINE_SLOTS},
+    legacy::{fixed_to_scaled, migrate_fixed_user_info},
+    state::{PoolInfo, PoolRewardMode, UserInfo},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+fn empty_pool() -> PoolInfo {
+    // SAFETY: plain old data, all zero is the state `create_pool` starts from
+    unsafe { std::mem::zeroed() }
+}
+
+fn new_user() -> UserInfo {
+    UserInfo {
+        version: ACCOUNT_VERSION,
+        ..UserInfo::default()
+    }
+}
+
+/// Pool and stakers driven through the same accounting calls the handlers make.
+struct Simulation {
+    pool: PoolInfo,
+    users: Vec<UserInfo>,
+    /// Rewards spread over a non-empty pool.
+    emitted: u128,
+    /// Whole tokens taken out of `accrued_reward`.
+    paid: u128,
+}
+
+impl Simulation {
+    fn new(users: usize) -> Self {
+        Self {
+            pool: empty_pool(),
+            users: vec![new_user(); users],
+            emitted: 0,
+            paid: 0,
+        }
+    }
+
+    fn accrue(&mut self, reward_amount: u64) {
+        if self.pool.lp_supply > 0 {
+            self.emitted += u128::from(reward_amount);
+            self.pool.reward_owed += reward_amount;
+        }
+        self.pool.accrue_reward(reward_amount).unwrap();
+    }
+
+    fn deposit(&mut self, index: usize, amount: u64) {
+        let user = &mut self.users[index];
+        user.settle(&self.pool).unwrap();
+        user.amount += amount;
+        self.pool.lp_supply += amount;
+        user.sync_reward_per_share(&self.pool);
+    }
+
+    fn withdraw(&mut self, index: usize, amount: u64) {
+        let user = &mut self.users[index];
+        let amount = amount.min(user.amount);
+        user.settle(&self.pool).unwrap();
+        user.amount -= amount;
+        self.pool.lp_supply -= amount;
+        user.sync_reward_per_share(&self.pool);
+    }
+
+    fn claim(&mut self, index: usize) -> u64 {
+        let user = &mut self.users[index];
+        user.settle(&self.pool).unwrap();
+        user.sync_reward_per_share(&self.pool);
+        let reward_amount = user.take_accrued_reward().unwrap();
+        self.paid += u128::from(reward_amount);
+        self.pool.reward_owed -= reward_amount;
+        reward_amount
+    }
+
+    fn sweep(&mut self) -> u64 {
+        let amount = self.pool.take_reward_dust().unwrap();
+        self.pool.reward_owed -= amount;
+        amount
+    }
+
+    fn claim_all(&mut self) {
+        for index in 0..self.users.len() {
+            self.claim(index);
+        }
+    }
+
+    /// Closes an empty account and opens a fresh one in its place.
+    fn close(&mut self, index: usize) {
+        let user = &mut self.users[index];
+        if user.is_empty() {
+            self.pool.forfeit_reward_dust(user.accrued_reward).unwrap();
+            *user = new_user();
+        }
+    }
+
+    /// Every scaled unit emitted is paid out, owed to a staker, carried by the pool or swept,
+    /// and `reward_owed` counts exactly what was neither paid nor swept.
+    fn assert_reconciles(&self) {
+        let accrued: u128 = self
+            .users
+            .iter()
+            .map(|user| {
+                let pending = (self.pool.acc_reward_per_share - user.reward_per_share_paid) * u128::from(user.amount);
+                user.accrued_reward + pending
+            })
+            .sum();
+        let swept = u128::from(self.pool.swept_reward_dust);
+        assert_eq!(
+            self.emitted * ACC_REWARD_PRECISION,
+            (self.paid + swept) * ACC_REWARD_PRECISION + accrued + self.pool.reward_remainder + self.pool.reward_dust
+        );
+        assert_eq!(self.emitted, self.paid + swept + u128::from(self.pool.reward_owed));
+    }
+}
+
+/// Staked amounts from dust to whale sized, so both rounding and large products get hit.
+fn random_amount(rng: &mut StdRng) -> u64 {
+    match rng.gen_range(0..4) {
+        0 => rng.gen_range(1..1_000),
+        1 => rng.gen_range(1..1_000_000_000),
+        2 => rng.gen_range(1..1_000_000_000_000_000_000),
+        _ => rng.gen_range(1..u64::MAX / 16),
+    }
+}
+
+#[test]
+fn random_operations_reconcile_with_emissions() {
+    for seed in 0..200 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut simulation = Simulation::new(rng.gen_range(1..6));
+        for _ in 0..300 {
+            let index = rng.gen_range(0..simulation.users.len());
+            match rng.gen_range(0..6) {
+                0 if simulation.pool.lp_supply < u64::MAX / 2 => {
+                    let amount = random_amount(&mut rng).min(u64::MAX / 2 - simulation.pool.lp_supply);
+                    simulation.deposit(index, amount);
+                }
+                1 => {
+                    let amount = random_amount(&mut rng);
+                    simulation.withdraw(index, amount);
+                }
+                2 => {
+                    simulation.claim(index);
+                }
+                3 => simulation.close(index),
+                4 => {
+                    simulation.sweep();
+                }
+                _ => {
+                    // emission per update stays far below a u64 token supply over the run
+                    let reward_amount = rng.gen_range(0..1_000_000_000_000_000);
+                    simulation.accrue(reward_amount);
+                }
+            }
+            simulation.assert_reconciles();
+        }
+        simulation.claim_all();
+        simulation.assert_reconciles();
+    }
+}
+
+#[test]
+fn small_rewards_reach_large_pools() {
+    // a billion 9 decimal LP tokens earning one base unit per slot
+    let mut simulation = Simulation::new(2);
+    simulation.deposit(0, 500_000_000_000_000_000);
+    simulation.deposit(1, 500_000_000_000_000_000);
+    for _ in 0..1_000 {
+        simulation.accrue(1);
+    }
+    assert_eq!(simulation.claim(0), 500);
+    assert_eq!(simulation.claim(1), 500);
+    simulation.assert_reconciles();
+}
+
+#[test]
+fn fractions_carry_over_to_later_claims() {
+    let mut simulation = Simulation::new(3);
+    for index in 0..3 {
+        simulation.deposit(index, 1);
+    }
+    // a third of a token per account and update, paid once whole
+    simulation.accrue(1);
+    assert_eq!(simulation.claim(0), 0);
+    simulation.accrue(1);
+    assert_eq!(simulation.claim(0), 0);
+    simulation.accrue(1);
+    assert_eq!(simulation.claim(0), 1);
+    assert_eq!(simulation.claim(1), 1);
+    assert_eq!(simulation.claim(2), 1);
+    assert_eq!(simulation.pool.reward_remainder, 0);
+    simulation.assert_reconciles();
+}
+
+#[test]
+fn dust_of_closed_accounts_is_swept_once_whole() {
+    let mut simulation = Simulation::new(4);
+    for index in 0..4 {
+        simulation.deposit(index, 1);
+    }
+    simulation.accrue(2);
+    for index in 0..4 {
+        simulation.withdraw(index, 1);
+        assert_eq!(simulation.claim(index), 0);
+        simulation.close(index);
+    }
+    // two tokens over four accounts leave each with half a token
+    assert_eq!(simulation.sweep(), 2);
+    assert_eq!(simulation.pool.swept_reward_dust, 2);
+    assert_eq!(simulation.pool.reward_dust, 0);
+    simulation.assert_reconciles();
+}
+
+#[test]
+fn emptied_pools_owe_nothing_once_accounts_close() {
+    let mut simulation = Simulation::new(3);
+    for index in 0..3 {
+        simulation.deposit(index, 2);
+    }
+    // leaves a remainder in the pool and a fraction in every account
+    simulation.accrue(10);
+    simulation.accrue(7);
+    assert!(simulation.pool.release_undistributed_reward().is_err());
+    for index in 0..3 {
+        simulation.withdraw(index, 2);
+    }
+    simulation.pool.release_undistributed_reward().unwrap();
+    assert!(simulation.pool.reward_owed > 0);
+
+    for index in 0..3 {
+        simulation.claim(index);
+        simulation.close(index);
+    }
+    simulation.pool.release_undistributed_reward().unwrap();
+    assert_eq!(simulation.pool.reward_owed, 0);
+    assert_eq!(simulation.pool.reward_remainder, 0);
+}
+
+#[test]
+fn unclaimed_rewards_are_forfeited_after_the_claim_deadline() {
+    let mut simulation = Simulation::new(2);
+    simulation.deposit(0, 1);
+    simulation.deposit(1, 1);
+    simulation.accrue(10);
+    simulation.withdraw(0, 1);
+    simulation.withdraw(1, 1);
+    simulation.claim(0);
+    simulation.pool.referral_owed = 3;
+    simulation.pool.close_slot = 100;
+
+    let deadline = 100 + CLAIM_DEADLINE_SLOTS;
+    assert_eq!(simulation.pool.forfeit_unclaimed_reward(deadline - 1).unwrap(), 0);
+    assert_eq!(simulation.pool.total_owed().unwrap(), 5 + 3);
+    assert_eq!(simulation.pool.forfeit_unclaimed_reward(deadline).unwrap(), 5 + 3);
+    assert_eq!(simulation.pool.reward_owed, 0);
+    assert_eq!(simulation.pool.referral_owed, 0);
+}
+
+#[test]
+fn excess_rewards_leave_owed_rewards_in_the_vault_except_in_mint_mode() {
+    let mut simulation = Simulation::new(1);
+    simulation.deposit(0, 1);
+    simulation.accrue(10);
+    simulation.withdraw(0, 1);
+    simulation.pool.referral_owed = 3;
+
+    assert_eq!(simulation.pool.excess_reward(20).unwrap(), 20 - 10 - 3);
+    assert_eq!(simulation.pool.excess_reward(5).unwrap(), 0);
+
+    // owed rewards are minted on claim, nothing in the vault backs them
+    simulation.pool.reward_mode = PoolRewardMode::Mint.into();
+    assert_eq!(simulation.pool.excess_reward(20).unwrap(), 20);
+    assert_eq!(simulation.pool.excess_reward(5).unwrap(), 5);
+}
+
+#[test]
+fn whale_stakes_do_not_overflow() {
+    let mut simulation = Simulation::new(2);
+    // a single staked unit collects half a token supply worth of rewards
+    simulation.deposit(0, 1);
+    simulation.accrue(u64::MAX / 2);
+    simulation.deposit(1, u64::MAX - 1);
+    simulation.accrue(u64::MAX / 2);
+    simulation.claim_all();
+    simulation.assert_reconciles();
+}
+
+#[test]
+fn unmigrated_accounts_are_rejected() {
+    let pool = empty_pool();
+    let mut user_info = UserInfo {
+        version: ACCOUNT_VERSION - 1,
+        ..UserInfo::default()
+    };
+    assert!(user_info.settle(&pool).is_err());
+}
+
+#[test]
+fn only_blank_legacy_accounts_are_stamped() {
+    let mut user_info = UserInfo::default();
+    user_info.check_version().unwrap();
+    assert_eq!(user_info.version, ACCOUNT_VERSION);
+
+    // an unstaked version 1 account still holding a pre-migration snapshot
+    let mut user_info = UserInfo {
+        version: 1,
+        reward_per_share_paid: 1 << 48,
+        ..UserInfo::default()
+    };
+    user_info.check_version().unwrap();
+    assert_eq!(user_info.version, ACCOUNT_VERSION);
+
+    for mut user_info in [
+        UserInfo { amount: 1, ..UserInfo::default() },
+        UserInfo { cooling_amount: 1, version: 1, ..UserInfo::default() },
+        UserInfo { accrued_reward: 1, ..UserInfo::default() },
+    ] {
+        assert!(user_info.check_version().is_err());
+        assert_ne!(user_info.version, ACCOUNT_VERSION);
+    }
+}
+
+#[test]
+fn legacy_reward_fractions_keep_accounts_open() {
+    // about 2 tokens as `I80F48` bits, below ACC_REWARD_PRECISION as a raw number
+    let legacy_accrued = 2u128 << 48;
+    assert!(legacy_accrued < ACC_REWARD_PRECISION);
+    let mut user_info = UserInfo {
+        accrued_reward: legacy_accrued,
+        ..UserInfo::default()
+    };
+    assert!(!user_info.is_empty());
+
+    migrate_fixed_user_info(&mut user_info, 0).unwrap();
+    user_info.version = ACCOUNT_VERSION;
+    assert!(!user_info.is_empty());
+    assert_eq!(user_info.take_accrued_reward().unwrap(), 2);
+    assert!(user_info.is_empty());
+
+    // a fraction of a token left after migration can be closed
+    user_info.accrued_reward = ACC_REWARD_PRECISION - 1;
+    assert!(user_info.is_empty());
+}
+
+#[test]
+fn fixed_point_values_convert_without_favoring_users() {
+    let one = 1i128 << 48;
+    assert_eq!(fixed_to_scaled(one * 3 / 2, false), Some(1_500_000_000_000_000_000));
+    // 2^-48 is 3552.71... in 1e-18 units
+    assert_eq!(fixed_to_scaled(1, false), Some(3_552));
+    assert_eq!(fixed_to_scaled(1, true), Some(3_553));
+    assert_eq!(fixed_to_scaled(-1, false), None);
+
+    // reward debt of 10 units synced at 2.25 per share, with 4.5 accrued
+    let acc_reward_per_share = fixed_to_scaled(one * 9 / 4, false).unwrap();
+    let mut user_info = UserInfo {
+        amount: 10,
+        reward_per_share_paid: (one * 9 / 4 * 10) as u128,
+        accrued_reward: (one * 9 / 2) as u128,
+        ..UserInfo::default()
+    };
+    migrate_fixed_user_info(&mut user_info, acc_reward_per_share).unwrap();
+    assert_eq!(user_info.reward_per_share_paid, 2_250_000_000_000_000_000);
+    assert_eq!(user_info.accrued_reward, 4_500_000_000_000_000_000);
+
+    // a debt rounded above the converted accumulator is capped, leaving nothing pending
+    let mut user_info = UserInfo {
+        amount: 3,
+        reward_per_share_paid: 1,
+        ..UserInfo::default()
+    };
+    migrate_fixed_user_info(&mut user_info, 0).unwrap();
+    assert_eq!(user_info.reward_per_share_paid, 0);
+}
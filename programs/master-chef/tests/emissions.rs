@@ -0,0 +1,88 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anchor_lang::prelude::Pubkey;
+use master_chef::{
+    constants::ACC_REWARD_PRECISION,
+    state::{EmissionSegment, PoolInfo},
+};
+use solana_program::{
+    clock::Clock,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+};
+
+static SLOT: AtomicU64 = AtomicU64::new(0);
+
+/// Serves `Clock::get` at `SLOT` outside the runtime.
+struct ClockStub;
+
+impl SyscallStubs for ClockStub {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            slot: SLOT.load(Ordering::SeqCst),
+            ..Clock::default()
+        };
+        // SAFETY: `Clock::get` passes a pointer to a `Clock`
+        unsafe { *(var_addr as *mut Clock) = clock };
+        solana_program::entrypoint::SUCCESS
+    }
+}
+
+fn empty_pool() -> PoolInfo {
+    // SAFETY: plain old data, all zero is the state `create_pool` starts from
+    unsafe { std::mem::zeroed() }
+}
+
+#[test]
+fn pool_updates_accumulate_rewards_per_share() {
+    set_syscall_stubs(Box::new(ClockStub));
+    let mut pool = empty_pool();
+    pool.reward_per_slot = 3;
+    pool.lp_supply = 6;
+
+    SLOT.store(10, Ordering::SeqCst);
+    pool.update_pool(Pubkey::default()).unwrap();
+    assert_eq!(pool.acc_reward_per_share, 5 * ACC_REWARD_PRECISION);
+    SLOT.store(30, Ordering::SeqCst);
+    pool.update_pool(Pubkey::default()).unwrap();
+    assert_eq!(pool.acc_reward_per_share, 15 * ACC_REWARD_PRECISION);
+    assert_eq!(pool.reward_owed, 90);
+    assert_eq!(pool.last_reward_slot, 30);
+}
+
+#[test]
+fn closing_pools_stop_scheduled_emissions() {
+    let mut pool = empty_pool();
+    pool.reward_per_slot = 8;
+    pool.set_emission_schedule(
+        &[EmissionSegment { start_slot: 100, reward_per_slot: 16 }],
+        1_000,
+    )
+    .unwrap();
+    assert_eq!(pool.rewards_between(90, 110).unwrap(), 10 * 8 + 10 * 16);
+    assert_eq!(pool.rewards_between(1_000, 1_010).unwrap(), 10 * 8);
+
+    pool.reward_per_slot = 0;
+    pool.close_slot = 200;
+    assert_eq!(pool.reward_per_slot_at(150), 0);
+    assert_eq!(pool.rewards_between(90, 2_010).unwrap(), 0);
+}
+
+#[test]
+fn bonus_multiplier_applies_until_bonus_end_slot() {
+    let mut pool = empty_pool();
+    pool.bonus_end_slot = 100;
+    pool.bonus_multiplier = 10;
+    assert_eq!(pool.get_multiplier(90, 110).unwrap(), 10 * 10 + 10);
+    assert_eq!(pool.get_multiplier(100, 110).unwrap(), 10);
+    assert_eq!(pool.get_multiplier(0, 50).unwrap(), 500);
+}
+
+#[test]
+fn empty_slot_ranges_have_no_multiplier() {
+    let mut pool = empty_pool();
+    pool.bonus_end_slot = 100;
+    pool.bonus_multiplier = 10;
+    assert_eq!(pool.get_multiplier(50, 50).unwrap(), 0);
+    assert_eq!(pool.get_multiplier(120, 110).unwrap(), 0);
+    assert_eq!(pool.get_multiplier(60, 40).unwrap(), 0);
+}
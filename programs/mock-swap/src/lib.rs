@@ -0,0 +1,171 @@
+#![allow(clippy::result_large_err)]
+
+//! Fixed-rate swap used to exercise the compound-vault swap interface in tests.
+//!
+//! Every pool converts `input_mint` into `output_mint` at `rate_numerator / rate_denominator`,
+//! paying out of an output reserve that has to be funded up front.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+
+declare_id!("23GeU7XdP3NDoEvzpuDDJfTBQw3KPbDeDTYYzUagv9jx");
+
+pub const POOL_SEED: &str = "pool";
+
+#[program]
+pub mod mock_swap {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, rate_numerator: u64, rate_denominator: u64) -> Result<()> {
+        require_gt!(rate_denominator, 0);
+        let pool = &mut ctx.accounts.pool;
+        pool.input_mint = ctx.accounts.input_mint.key();
+        pool.output_mint = ctx.accounts.output_mint.key();
+        pool.rate_numerator = rate_numerator;
+        pool.rate_denominator = rate_denominator;
+        pool.bump = *ctx.bumps.get("pool").unwrap();
+        Ok(())
+    }
+
+    /// Implements the compound-vault swap interface: `authority`, `source`, `destination`
+    /// and `token_program` come first, the accounts of this program follow.
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        let amount_out = (u128::from(amount_in) * u128::from(pool.rate_numerator) / u128::from(pool.rate_denominator)) as u64;
+        require_gte!(amount_out, min_amount_out, SwapError::SlippageExceeded);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source.to_account_info(),
+                    to: ctx.accounts.input_reserve.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount_in,
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.output_reserve.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&[
+                    POOL_SEED.as_bytes(),
+                    pool.input_mint.as_ref(),
+                    pool.output_mint.as_ref(),
+                    &[pool.bump],
+                ]],
+            ),
+            amount_out,
+        )
+    }
+}
+
+#[error_code]
+pub enum SwapError {
+    #[msg("Swap output is below the minimum")]
+    SlippageExceeded,
+}
+
+#[account]
+#[derive(Default)]
+pub struct Pool {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub rate_numerator: u64,
+    pub rate_denominator: u64,
+    pub bump: u8,
+}
+
+impl Pool {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub input_mint: Account<'info, Mint>,
+
+    pub output_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Pool::LEN,
+        seeds = [
+            POOL_SEED.as_bytes(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = input_mint,
+        associated_token::authority = pool,
+    )]
+    pub input_reserve: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = output_mint,
+        associated_token::authority = pool,
+    )]
+    pub output_reserve: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+
+    pub authority: Signer<'info>,
+
+    #[account(mut, token::mint = pool.input_mint)]
+    pub source: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = pool.output_mint)]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        seeds = [
+            POOL_SEED.as_bytes(),
+            pool.input_mint.as_ref(),
+            pool.output_mint.as_ref(),
+        ],
+        bump = pool.bump,
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool.input_mint,
+        associated_token::authority = pool,
+    )]
+    pub input_reserve: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = pool.output_mint,
+        associated_token::authority = pool,
+    )]
+    pub output_reserve: Account<'info, TokenAccount>,
+}
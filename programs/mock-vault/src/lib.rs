@@ -0,0 +1,385 @@
+#![allow(clippy::result_large_err)]
+
+//! Minimal vault staking LP tokens into master-chef on behalf of its depositors.
+//!
+//! The vault PDA is the master-chef `user` and signs every CPI, while the depositor pays
+//! the rent of the accounts master-chef creates. It only supports open pools paying
+//! rewards from their vault, without receipt mint, and keeps harvested rewards itself.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use master_chef::{cpi::accounts as master_chef_accounts, program::MasterChef};
+
+declare_id!("4EPrYDWq5h9x6gMMi5aqT6Kkkb4NDYHfdsgEgfCYRGt6");
+
+pub const VAULT_SEED: &str = "vault";
+
+pub const DEPOSITOR_SEED: &str = "depositor";
+
+macro_rules! vault_seeds {
+    ($vault: expr) => {
+        &[
+            VAULT_SEED.as_bytes(),
+            $vault.master_chef.as_ref(),
+            $vault.lp_token.as_ref(),
+            &[$vault.bump],
+        ]
+    };
+}
+
+#[program]
+pub mod mock_vault {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.master_chef = ctx.accounts.master_chef.key();
+        vault.lp_token = ctx.accounts.lp_mint.key();
+        vault.bump = *ctx.bumps.get("vault").unwrap();
+        Ok(())
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_lp_token_account.to_account_info(),
+                    to: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let vault = &ctx.accounts.vault;
+        let seeds = vault_seeds!(vault);
+        master_chef::cpi::deposit(
+            CpiContext::new_with_signer(
+                ctx.accounts.master_chef_program.to_account_info(),
+                master_chef_accounts::Deposit {
+                    user: vault.to_account_info(),
+                    payer: ctx.accounts.user.to_account_info(),
+                    master_chef: ctx.accounts.master_chef.to_account_info(),
+                    user_lp_token_account: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    user_info: ctx.accounts.vault_user_info.to_account_info(),
+                    allowlist_entry: None,
+                    referrer_info: None,
+                    lp_token_vault: ctx.accounts.lp_token_vault.to_account_info(),
+                    receipt_mint: None,
+                    user_receipt_token_account: None,
+                    mint_authority: None,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[seeds],
+            ),
+            vault.lp_token,
+            amount,
+            vec![],
+        )?;
+
+        ctx.accounts.depositor.amount += amount;
+        ctx.accounts.vault.total_amount += amount;
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require_gte!(ctx.accounts.depositor.amount, amount);
+
+        let vault = &ctx.accounts.vault;
+        let seeds = vault_seeds!(vault);
+        master_chef::cpi::withdraw(
+            CpiContext::new_with_signer(
+                ctx.accounts.master_chef_program.to_account_info(),
+                master_chef_accounts::Withdraw {
+                    user: vault.to_account_info(),
+                    master_chef: ctx.accounts.master_chef.to_account_info(),
+                    user_lp_token_account: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    user_info: ctx.accounts.vault_user_info.to_account_info(),
+                    lp_token_vault: ctx.accounts.lp_token_vault.to_account_info(),
+                    lp_token_vault_authority: ctx.accounts.lp_token_vault_authority.to_account_info(),
+                    receipt_mint: None,
+                    user_receipt_token_account: None,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[seeds],
+            ),
+            vault.lp_token,
+            amount,
+        )?;
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_lp_token_account.to_account_info(),
+                    to: ctx.accounts.user_lp_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.depositor.amount -= amount;
+        ctx.accounts.vault.total_amount -= amount;
+        Ok(())
+    }
+
+    /// Claims the vault's rewards into its own reward token account. Anyone can call it.
+    pub fn harvest(ctx: Context<Harvest>) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let seeds = vault_seeds!(vault);
+        master_chef::cpi::claim_reward(
+            CpiContext::new_with_signer(
+                ctx.accounts.master_chef_program.to_account_info(),
+                master_chef_accounts::ClaimReward {
+                    master_chef: ctx.accounts.master_chef.to_account_info(),
+                    user: vault.to_account_info(),
+                    payer: ctx.accounts.payer.to_account_info(),
+                    reward_mint: ctx.accounts.reward_mint.to_account_info(),
+                    user_reward_token_account: ctx.accounts.vault_reward_token_account.to_account_info(),
+                    user_info: ctx.accounts.vault_user_info.to_account_info(),
+                    referrer_info: None,
+                    reward_token_vault: ctx.accounts.reward_token_vault.to_account_info(),
+                    reward_token_vault_authority: ctx.accounts.reward_token_vault_authority.to_account_info(),
+                    mint_authority: None,
+                    dev_fund_token_account: None,
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[seeds],
+            ),
+            vault.lp_token,
+        )
+    }
+}
+
+#[account]
+#[derive(Default)]
+pub struct Vault {
+    pub master_chef: Pubkey,
+    pub lp_token: Pubkey,
+    /// LP tokens staked through the vault.
+    pub total_amount: u64,
+    pub bump: u8,
+}
+
+impl Vault {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+#[account]
+#[derive(Default)]
+pub struct Depositor {
+    pub amount: u64,
+}
+
+impl Depositor {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Only used as a seed, validated by master-chef on every CPI
+    pub master_chef: UncheckedAccount<'info>,
+
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Vault::LEN,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            master_chef.key().as_ref(),
+            lp_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            vault.master_chef.as_ref(),
+            vault.lp_token.as_ref(),
+        ],
+        bump = vault.bump,
+        has_one = master_chef,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = Depositor::LEN,
+        seeds = [
+            DEPOSITOR_SEED.as_bytes(),
+            vault.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub depositor: Account<'info, Depositor>,
+
+    #[account(mut)]
+    pub user_lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.lp_token,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub master_chef: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub vault_user_info: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub lp_token_vault: UncheckedAccount<'info>,
+
+    pub master_chef_program: Program<'info, MasterChef>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            vault.master_chef.as_ref(),
+            vault.lp_token.as_ref(),
+        ],
+        bump = vault.bump,
+        has_one = master_chef,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [
+            DEPOSITOR_SEED.as_bytes(),
+            vault.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub depositor: Account<'info, Depositor>,
+
+    #[account(mut)]
+    pub user_lp_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = vault.lp_token,
+        associated_token::authority = vault,
+    )]
+    pub vault_lp_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub master_chef: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub vault_user_info: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub lp_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    pub lp_token_vault_authority: UncheckedAccount<'info>,
+
+    pub master_chef_program: Program<'info, MasterChef>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            VAULT_SEED.as_bytes(),
+            vault.master_chef.as_ref(),
+            vault.lp_token.as_ref(),
+        ],
+        bump = vault.bump,
+        has_one = master_chef,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub master_chef: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Created and validated by master-chef
+    #[account(mut)]
+    pub vault_reward_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub vault_user_info: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub reward_token_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by master-chef
+    #[account(mut)]
+    pub reward_token_vault_authority: UncheckedAccount<'info>,
+
+    pub master_chef_program: Program<'info, MasterChef>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,269 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, Wallet } from "@coral-xyz/anchor";
+import { MasterChef } from "../target/types/master_chef";
+import { CompoundVault } from "../target/types/compound_vault";
+import { MockSwap } from "../target/types/mock_swap";
+import { Keypair, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync, createMint, mintTo, createAccount, getAccount } from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("compound-vault", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = provider.wallet as Wallet;
+  const program = anchor.workspace.MasterChef as Program<MasterChef>;
+  const vaultProgram = anchor.workspace.CompoundVault as Program<CompoundVault>;
+  const swapProgram = anchor.workspace.MockSwap as Program<MockSwap>;
+
+  let masterChef = Keypair.generate();
+  let rewardMint: PublicKey;
+  let lpMint: PublicKey;
+  let userLpTokenAccount: PublicKey;
+  let userShareTokenAccount: PublicKey;
+  let vault: PublicKey;
+  let shareMint: PublicKey;
+  let vaultUserInfo: PublicKey;
+  let swapPool: PublicKey;
+  let swapAuthority: PublicKey;
+
+  let poolAddress = (seed: string) => PublicKey.findProgramAddressSync(
+    [Buffer.from(seed), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+    program.programId
+  )[0];
+
+  let stakeAccounts = () => ({
+    vault,
+    shareMint,
+    vaultLpTokenAccount: getAssociatedTokenAddressSync(lpMint, vault, true),
+    masterChef: masterChef.publicKey,
+    vaultUserInfo,
+    lpTokenVault: poolAddress("lp_token_vault"),
+    masterChefProgram: program.programId,
+  });
+
+  let userAccounts = () => ({
+    ...stakeAccounts(),
+    user: payer.publicKey,
+    userShareTokenAccount,
+    userLpTokenAccount,
+    instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+  });
+
+  before(async () => {
+    rewardMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    lpMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    userLpTokenAccount = await createAccount(provider.connection, payer.payer, lpMint, payer.publicKey);
+    await mintTo(provider.connection, payer.payer, lpMint, userLpTokenAccount, payer.payer, 1000);
+
+    [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), masterChef.publicKey.toBuffer(), lpMint.toBuffer()],
+      vaultProgram.programId
+    );
+    [shareMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), vault.toBuffer()],
+      vaultProgram.programId
+    );
+    [swapAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("swap_authority"), vault.toBuffer()],
+      vaultProgram.programId
+    );
+    [vaultUserInfo] = PublicKey.findProgramAddressSync(
+      [vault.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    [swapPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), rewardMint.toBuffer(), lpMint.toBuffer()],
+      swapProgram.programId
+    );
+
+    await program.methods
+      .initialize()
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .signers([masterChef])
+      .rpc();
+    await program.methods
+      .addPool(rewardMint, lpMint, new anchor.BN(10), new anchor.BN(0))
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint,
+        rewardMint,
+        rewardTokenVault: poolAddress("reward_token_vault"),
+        rewardTokenVaultAuthority: poolAddress("reward_token_vault_auth"),
+        lpTokenVault: poolAddress("lp_token_vault"),
+        lpTokenVaultAuthority: poolAddress("lp_token_vault_auth"),
+      })
+      .rpc();
+    await mintTo(provider.connection, payer.payer, rewardMint, poolAddress("reward_token_vault"), payer.publicKey, 20000000);
+
+    // rewards swap 1:1 into LP tokens out of a pre-funded reserve
+    await swapProgram.methods
+      .initialize(new anchor.BN(1), new anchor.BN(1))
+      .accounts({
+        payer: payer.publicKey,
+        inputMint: rewardMint,
+        outputMint: lpMint,
+        pool: swapPool,
+        inputReserve: getAssociatedTokenAddressSync(rewardMint, swapPool, true),
+        outputReserve: getAssociatedTokenAddressSync(lpMint, swapPool, true),
+      })
+      .rpc();
+    await mintTo(provider.connection, payer.payer, lpMint, getAssociatedTokenAddressSync(lpMint, swapPool, true), payer.payer, 20000000);
+  });
+
+  let compoundAccounts = (keeper: PublicKey) => ({
+    ...stakeAccounts(),
+    payer: payer.publicKey,
+    keeper,
+    vaultRewardTokenAccount: getAssociatedTokenAddressSync(rewardMint, vault, true),
+    rewardMint,
+    rewardTokenVault: poolAddress("reward_token_vault"),
+    rewardTokenVaultAuthority: poolAddress("reward_token_vault_auth"),
+    swapProgram: swapProgram.programId,
+    swapAuthority,
+  });
+
+  let swapRemainingAccounts = () => [
+    { pubkey: swapPool, isSigner: false, isWritable: false },
+    { pubkey: getAssociatedTokenAddressSync(rewardMint, swapPool, true), isSigner: false, isWritable: true },
+    { pubkey: getAssociatedTokenAddressSync(lpMint, swapPool, true), isSigner: false, isWritable: true },
+  ];
+
+  let compoundInstruction = () => vaultProgram.methods
+    .compound(new anchor.BN(0))
+    .accounts(compoundAccounts(payer.publicKey))
+    .remainingAccounts(swapRemainingAccounts())
+    .instruction();
+
+  it("initialize vault!", async () => {
+    const stranger = Keypair.generate();
+    try {
+      await vaultProgram.methods
+        .initialize(stranger.publicKey)
+        .accounts({
+          payer: payer.publicKey,
+          admin: stranger.publicKey,
+          masterChef: masterChef.publicKey,
+          lpMint,
+          rewardMint,
+          swapProgram: swapProgram.programId,
+          vault,
+          shareMint,
+          vaultLpTokenAccount: getAssociatedTokenAddressSync(lpMint, vault, true),
+          vaultRewardTokenAccount: getAssociatedTokenAddressSync(rewardMint, vault, true),
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("only the master-chef admin can create vaults");
+    } catch (err) {
+      assert.include(`${err}`, "ConstraintHasOne");
+    }
+
+    const tx = await vaultProgram.methods
+      .initialize(payer.publicKey)
+      .accounts({
+        payer: payer.publicKey,
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint,
+        rewardMint,
+        swapProgram: swapProgram.programId,
+        vault,
+        shareMint,
+        vaultLpTokenAccount: getAssociatedTokenAddressSync(lpMint, vault, true),
+        vaultRewardTokenAccount: getAssociatedTokenAddressSync(rewardMint, vault, true),
+      })
+      .rpc();
+    userShareTokenAccount = await createAccount(provider.connection, payer.payer, shareMint, payer.publicKey);
+    console.log(`initialize vault success, transaction signature is:`, tx);
+  });
+
+  it("deposit!", async () => {
+    // the vault has no shares yet, so nothing needs compounding first
+    const tx = await vaultProgram.methods
+      .deposit(new anchor.BN("300"))
+      .accounts(userAccounts())
+      .rpc();
+    const shares = await getAccount(provider.connection, userShareTokenAccount);
+    assert.equal(Number(shares.amount), 300);
+    console.log(`deposit success, transaction signature is:`, tx);
+  });
+
+  it("compound!", async () => {
+    const stranger = Keypair.generate();
+    try {
+      await vaultProgram.methods
+        .compound(new anchor.BN(0))
+        .accounts(compoundAccounts(stranger.publicKey))
+        .remainingAccounts(swapRemainingAccounts())
+        .signers([stranger])
+        .rpc();
+      assert.fail("only the keeper can compound");
+    } catch (err) {
+      assert.include(`${err}`, "ConstraintHasOne");
+    }
+
+    const tx = await vaultProgram.methods
+      .compound(new anchor.BN(1))
+      .accounts(compoundAccounts(payer.publicKey))
+      .remainingAccounts(swapRemainingAccounts())
+      .rpc();
+    const vaultAccount = await vaultProgram.account.vault.fetch(vault);
+    assert.isAbove(vaultAccount.totalStaked.toNumber(), 300);
+    const rewards = await getAccount(provider.connection, getAssociatedTokenAddressSync(rewardMint, vault, true));
+    assert.isNull(rewards.delegate);
+    console.log(`compound success, transaction signature is:`, tx);
+  });
+
+  it("deposit before compound!", async () => {
+    // rewards earned since the last compound are not staked yet, shares priced without them
+    // would be too cheap
+    try {
+      await vaultProgram.methods
+        .deposit(new anchor.BN("100"))
+        .accounts(userAccounts())
+        .rpc();
+      assert.fail("deposits must follow a compound");
+    } catch (err) {
+      assert.include(`${err}`, "NotCompounded");
+    }
+
+    const sharesBefore = Number((await getAccount(provider.connection, userShareTokenAccount)).amount);
+    const tx = await vaultProgram.methods
+      .deposit(new anchor.BN("100"))
+      .accounts(userAccounts())
+      .preInstructions([await compoundInstruction()])
+      .rpc();
+    const minted = Number((await getAccount(provider.connection, userShareTokenAccount)).amount) - sharesBefore;
+    const vaultAccount = await vaultProgram.account.vault.fetch(vault);
+    const stakedBeforeDeposit = vaultAccount.totalStaked.toNumber() - 100;
+    assert.equal(minted, Math.floor(100 * sharesBefore / stakedBeforeDeposit));
+    assert.isBelow(minted, 100);
+    console.log(`deposit before compound success, transaction signature is:`, tx);
+  });
+
+  it("withdraw!", async () => {
+    try {
+      await vaultProgram.methods
+        .withdraw(new anchor.BN("300"))
+        .accounts({ ...userAccounts(), lpTokenVaultAuthority: poolAddress("lp_token_vault_auth") })
+        .rpc();
+      assert.fail("withdrawals must follow a compound");
+    } catch (err) {
+      assert.include(`${err}`, "NotCompounded");
+    }
+
+    const shares = await getAccount(provider.connection, userShareTokenAccount);
+    const tx = await vaultProgram.methods
+      .withdraw(new anchor.BN(shares.amount.toString()))
+      .accounts({ ...userAccounts(), lpTokenVaultAuthority: poolAddress("lp_token_vault_auth") })
+      .preInstructions([await compoundInstruction()])
+      .rpc();
+    const lp = await getAccount(provider.connection, userLpTokenAccount);
+    assert.isAbove(Number(lp.amount), 1000);
+    console.log(`withdraw success, transaction signature is:`, tx);
+  });
+});
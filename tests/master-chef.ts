@@ -3,6 +3,7 @@ import { Program, Wallet } from "@coral-xyz/anchor";
 import { MasterChef } from "../target/types/master_chef";
 import { Keypair, PublicKey, } from "@solana/web3.js";
 import { getAssociatedTokenAddressSync, createMint, mintTo, createAccount } from "@solana/spl-token";
+import { assert } from "chai";
 
 
 import * as dotenv from "dotenv";
@@ -167,6 +168,22 @@ describe("master-chef", () => {
     console.log(`update reward per slot success, transaction signature is:`, tx);
   });
 
+  it("set emission schedule!", async () => {
+    const slot = await provider.connection.getSlot();
+    let segments = [
+      { startSlot: new anchor.BN(slot + 1000), rewardPerSlot: new anchor.BN("2") },
+      { startSlot: new anchor.BN(slot + 2000), rewardPerSlot: new anchor.BN("1") },
+    ];
+    const tx = await program.methods
+      .setEmissionSchedule(lpMint, segments, new anchor.BN(0))
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .rpc();
+    console.log(`set emission schedule success, transaction signature is:`, tx);
+  });
+
   it("set admin!", async () => {
     let config = {
       admin: payer.publicKey
@@ -181,21 +198,71 @@ describe("master-chef", () => {
     console.log(`set admin success, transaction signature is:`, tx);
   });
 
+  it("configure pool!", async () => {
+    let config = {
+      maxTotalDeposit: new anchor.BN("1000"),
+      maxDepositPerUser: new anchor.BN("500"),
+      bonusEndSlot: new anchor.BN(await provider.connection.getSlot() + 100),
+      bonusMultiplier: new anchor.BN("10"),
+    }
+    const tx = await program.methods
+      .configurePool(lpMint, config)
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .rpc();
+    console.log(`configure pool success, transaction signature is:`, tx);
+  });
+
+  it("allowlist!", async () => {
+    let [allowlistEntry] = PublicKey.findProgramAddressSync(
+      [Buffer.from("allowlist"), lpMint.toBuffer(), masterChef.publicKey.toBuffer(), payer.publicKey.toBuffer()],
+      program.programId
+    );
+    const tx = await program.methods
+      .addToAllowlist(lpMint, payer.publicKey)
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        allowlistEntry,
+      })
+      .postInstructions([
+        await program.methods
+          .removeFromAllowlist(lpMint, payer.publicKey)
+          .accounts({
+            admin: payer.publicKey,
+            masterChef: masterChef.publicKey,
+            allowlistEntry,
+          })
+          .instruction(),
+      ])
+      .rpc();
+    console.log(`allowlist success, transaction signature is:`, tx);
+  });
+
   it("depsoit!", async () => {
     const tx = await program.methods
-      .deposit(lpMint, new anchor.BN("200"))
+      .deposit(lpMint, new anchor.BN("200"), [])
       .accounts({
         masterChef: masterChef.publicKey,
         lpTokenVault,
         user: payer.publicKey,
+        payer: payer.publicKey,
         userInfo: userInfoAccount,
         userLpTokenAccount,
+        allowlistEntry: null,
+        referrerInfo: null,
+        receiptMint: null,
+        userReceiptTokenAccount: null,
+        mintAuthority: null,
       })
       .rpc();
     console.log(`deposit success, transaction signature is:`, tx);
   });
 
   it("withdraw!", async () => {
+    const before = await program.account.userInfo.fetch(userInfoAccount);
     const tx = await program.methods
       .withdraw(lpMint, new anchor.BN("100"))
       .accounts({
@@ -205,11 +272,89 @@ describe("master-chef", () => {
         userInfo: userInfoAccount,
         lpTokenVaultAuthority,
         userLpTokenAccount,
+        receiptMint: null,
+        userReceiptTokenAccount: null,
       })
       .rpc();
+    // the withdrawn amount has to be persisted on user_info, not only reported in the event
+    const after = await program.account.userInfo.fetch(userInfoAccount);
+    assert.equal(after.amount.toNumber(), before.amount.toNumber() - 100);
     console.log(`withdraw success, transaction signature is:`, tx);
   });
 
+  it("transfer position!", async () => {
+    let recipient = Keypair.generate().publicKey;
+    let [recipientInfo] = PublicKey.findProgramAddressSync(
+      [recipient.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    const tx = await program.methods
+      .transferPosition(lpMint, new anchor.BN("10"), [])
+      .accounts({
+        user: payer.publicKey,
+        recipient,
+        masterChef: masterChef.publicKey,
+        userInfo: userInfoAccount,
+        recipientInfo,
+        allowlistEntry: null,
+        receiptMint: null,
+        userReceiptTokenAccount: null,
+        recipientReceiptTokenAccount: null,
+        mintAuthority: null,
+      })
+      .postInstructions([
+        await program.methods
+          .deposit(lpMint, new anchor.BN("10"), [])
+          .accounts({
+            masterChef: masterChef.publicKey,
+            lpTokenVault,
+            user: payer.publicKey,
+            payer: payer.publicKey,
+            userInfo: userInfoAccount,
+            userLpTokenAccount,
+            allowlistEntry: null,
+            referrerInfo: null,
+            receiptMint: null,
+            userReceiptTokenAccount: null,
+            mintAuthority: null,
+          })
+          .instruction(),
+      ])
+      .rpc();
+    const recipientPosition = await program.account.userInfo.fetch(recipientInfo);
+    console.log(`recipient info: ${JSON.stringify(recipientPosition)}`);
+    console.log(`transfer position success, transaction signature is:`, tx);
+  });
+
+  it("request and complete withdraw!", async () => {
+    const tx = await program.methods
+      .requestWithdraw(lpMint, new anchor.BN("50"))
+      .accounts({
+        user: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        userInfo: userInfoAccount,
+        receiptMint: null,
+        userReceiptTokenAccount: null,
+      })
+      .postInstructions([
+        await program.methods
+          .completeWithdraw(lpMint)
+          .accounts({
+            user: payer.publicKey,
+            masterChef: masterChef.publicKey,
+            userLpTokenAccount,
+            userInfo: userInfoAccount,
+            lpTokenVault,
+            lpTokenVaultAuthority,
+          })
+          .instruction(),
+      ])
+      .rpc();
+    const userInfo = await program.account.userInfo.fetch(userInfoAccount);
+    console.log(`user info: ${JSON.stringify(userInfo)}`);
+    console.log(`request and complete withdraw success, transaction signature is:`, tx);
+  });
+
   it("claim reward!", async () => {
     let userRewardTokenAccount = getAssociatedTokenAddressSync(rewardMint, payer.publicKey);
     const tx = await program.methods
@@ -217,11 +362,15 @@ describe("master-chef", () => {
       .accounts({
         masterChef: masterChef.publicKey,
         user: payer.publicKey,
+        payer: payer.publicKey,
         userRewardTokenAccount,
         userInfo: userInfoAccount,
         rewardTokenVault,
         rewardTokenVaultAuthority,
         rewardMint,
+        referrerInfo: null,
+        mintAuthority: null,
+        devFundTokenAccount: null,
       })
       .rpc({skipPreflight: true});
     const userInfo = await program.account.userInfo.fetch(userInfoAccount);
@@ -229,4 +378,501 @@ describe("master-chef", () => {
     console.log(`claim reward success, transaction signature is:`, tx);
   }) 
 
+  it("referral!", async () => {
+    let [referrerInfo] = PublicKey.findProgramAddressSync(
+      [Buffer.from("referrer"), payer.publicKey.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    let referrerRewardTokenAccount = getAssociatedTokenAddressSync(rewardMint, payer.publicKey);
+    const tx = await program.methods
+      .registerReferrer(lpMint)
+      .accounts({
+        referrer: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        referrerInfo,
+      })
+      .postInstructions([
+        await program.methods
+          .claimReferralReward(lpMint)
+          .accounts({
+            masterChef: masterChef.publicKey,
+            referrer: payer.publicKey,
+            rewardMint,
+            referrerRewardTokenAccount,
+            referrerInfo,
+            rewardTokenVault,
+            rewardTokenVaultAuthority,
+            mintAuthority: null,
+            devFundTokenAccount: null,
+          })
+          .instruction(),
+      ])
+      .rpc();
+    const referrer = await program.account.referrerInfo.fetch(referrerInfo);
+    console.log(`referrer info: ${JSON.stringify(referrer)}`);
+    console.log(`referral success, transaction signature is:`, tx);
+  });
+
+  it("position!", async () => {
+    let positionMint = Keypair.generate();
+    let positionTokenAccount = getAssociatedTokenAddressSync(positionMint.publicKey, payer.publicKey);
+    let ownerRewardTokenAccount = getAssociatedTokenAddressSync(rewardMint, payer.publicKey);
+    let [positionInfo] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), positionMint.publicKey.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    let [mintAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    let positionAccounts = {
+      owner: payer.publicKey,
+      masterChef: masterChef.publicKey,
+      positionMint: positionMint.publicKey,
+      positionTokenAccount,
+      positionInfo,
+    };
+    // pools capping deposits per user take no position deposits
+    await program.methods
+      .configurePool(lpMint, { maxDepositPerUser: new anchor.BN(0) })
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .rpc();
+    const tx = await program.methods
+      .openPosition(lpMint)
+      .accounts({
+        ...positionAccounts,
+        mintAuthority,
+      })
+      .postInstructions([
+        await program.methods
+          .depositPosition(lpMint, new anchor.BN("10"), [])
+          .accounts({
+            ...positionAccounts,
+            userLpTokenAccount,
+            allowlistEntry: null,
+            lpTokenVault,
+          })
+          .instruction(),
+        await program.methods
+          .withdrawPosition(lpMint, new anchor.BN("10"))
+          .accounts({
+            ...positionAccounts,
+            userLpTokenAccount,
+            lpTokenVault,
+            lpTokenVaultAuthority,
+          })
+          .instruction(),
+        await program.methods
+          .claimPositionReward(lpMint)
+          .accounts({
+            ...positionAccounts,
+            rewardMint,
+            ownerRewardTokenAccount,
+            rewardTokenVault,
+            rewardTokenVaultAuthority,
+            mintAuthority: null,
+            devFundTokenAccount: null,
+          })
+          .instruction(),
+        await program.methods
+          .closePosition(lpMint)
+          .accounts(positionAccounts)
+          .instruction(),
+      ])
+      .signers([positionMint])
+      .rpc();
+    const position = await provider.connection.getAccountInfo(positionInfo);
+    console.log(`position closed: ${position === null}`);
+    console.log(`position success, transaction signature is:`, tx);
+  });
+
+  it("position pool settings!", async () => {
+    let positionMint = Keypair.generate();
+    let [positionInfo] = PublicKey.findProgramAddressSync(
+      [Buffer.from("position"), positionMint.publicKey.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    let [mintAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_authority"), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    let positionAccounts = {
+      owner: payer.publicKey,
+      masterChef: masterChef.publicKey,
+      positionMint: positionMint.publicKey,
+      positionTokenAccount: getAssociatedTokenAddressSync(positionMint.publicKey, payer.publicKey),
+      positionInfo,
+    };
+    const configure = (config: { unbondingSlots?: anchor.BN, maxDepositPerUser?: anchor.BN }) => program.methods
+      .configurePool(lpMint, config)
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .rpc();
+
+    // positions have no unbonding step and a per user cap could be split over positions
+    for (const [setting, reset] of [
+      [{ unbondingSlots: new anchor.BN(100) }, { unbondingSlots: new anchor.BN(0) }],
+      [{ maxDepositPerUser: new anchor.BN(500) }, { maxDepositPerUser: new anchor.BN(0) }],
+    ]) {
+      await configure(setting);
+      try {
+        await program.methods
+          .openPosition(lpMint)
+          .accounts({
+            ...positionAccounts,
+            mintAuthority,
+          })
+          .postInstructions([
+            await program.methods
+              .depositPosition(lpMint, new anchor.BN("10"), [])
+              .accounts({
+                ...positionAccounts,
+                userLpTokenAccount,
+                allowlistEntry: null,
+                lpTokenVault,
+              })
+              .instruction(),
+          ])
+          .signers([positionMint])
+          .rpc();
+        throw new Error(`position deposit succeeded with ${JSON.stringify(setting)}`);
+      } catch (err) {
+        if (!(err instanceof anchor.AnchorError) || err.error.errorCode.code !== "UnsupportedPositionPool") {
+          throw err;
+        }
+        console.log(`position deposit rejected: ${err.error.errorMessage}`);
+      } finally {
+        await configure(reset);
+      }
+    }
+  });
+
+  it("close user info!", async () => {
+    let userRewardTokenAccount = getAssociatedTokenAddressSync(rewardMint, payer.publicKey);
+    const tx = await program.methods
+      .closeUserInfo(lpMint)
+      .accounts({
+        user: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        userInfo: userInfoAccount,
+      })
+      .preInstructions([
+        await program.methods
+          .withdraw(lpMint, new anchor.BN("50"))
+          .accounts({
+            masterChef: masterChef.publicKey,
+            lpTokenVault,
+            user: payer.publicKey,
+            userInfo: userInfoAccount,
+            lpTokenVaultAuthority,
+            userLpTokenAccount,
+            receiptMint: null,
+            userReceiptTokenAccount: null,
+          })
+          .instruction(),
+        await program.methods
+          .claimReward(lpMint)
+          .accounts({
+            masterChef: masterChef.publicKey,
+            user: payer.publicKey,
+            payer: payer.publicKey,
+            userRewardTokenAccount,
+            userInfo: userInfoAccount,
+            rewardTokenVault,
+            rewardTokenVaultAuthority,
+            rewardMint,
+            referrerInfo: null,
+            mintAuthority: null,
+            devFundTokenAccount: null,
+          })
+          .instruction(),
+      ])
+      .rpc();
+    const userInfo = await provider.connection.getAccountInfo(userInfoAccount);
+    console.log(`user info closed: ${userInfo === null}`);
+    console.log(`close user info success, transaction signature is:`, tx);
+  })
+
+  it("sweep reward dust!", async () => {
+    const masterChefAccount = await program.account.masterChef.fetch(masterChef.publicKey);
+    const pool = masterChefAccount.pools.find((pool) => pool.initialized && pool.lpToken.equals(lpMint))!;
+    console.log(`reward dust: ${pool.rewardDust.toString()}, remainder: ${pool.rewardRemainder.toString()}`);
+    try {
+      const tx = await program.methods
+        .sweepRewardDust(lpMint)
+        .accounts({
+          admin: payer.publicKey,
+          masterChef: masterChef.publicKey,
+        })
+        .rpc();
+      console.log(`sweep reward dust success, transaction signature is:`, tx);
+    } catch (err) {
+      // closing only leaves a fraction of a token behind unless many accounts were closed
+      if (!(err instanceof anchor.AnchorError) || err.error.errorCode.code !== "NoRewardDust") {
+        throw err;
+      }
+      console.log(`no whole token of reward dust to sweep`);
+    }
+  })
+
+  it("close pool!", async () => {
+    let pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let poolSeeds = (seed: string, mint: PublicKey) => pda([Buffer.from(seed), mint.toBuffer(), masterChef.publicKey.toBuffer()]);
+    let addPool = (mint: PublicKey) => program.methods
+      .addPool(rewardMint, mint, new anchor.BN(0), new anchor.BN(0))
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint: mint,
+        rewardMint,
+        rewardTokenVault: poolSeeds("reward_token_vault", mint),
+        rewardTokenVaultAuthority: poolSeeds("reward_token_vault_auth", mint),
+        lpTokenVault: poolSeeds("lp_token_vault", mint),
+        lpTokenVaultAuthority: poolSeeds("lp_token_vault_auth", mint),
+      })
+      .rpc();
+    let closeLpMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    let lpTreasury = await createAccount(provider.connection, payer.payer, closeLpMint, payer.publicKey);
+    let treasury = await createAccount(provider.connection, payer.payer, rewardMint, payer.publicKey, Keypair.generate());
+    await addPool(closeLpMint);
+
+    await program.methods
+      .closePool(closeLpMint, new anchor.BN(0))
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .rpc();
+    // a stray LP token sent to the vault must not keep it from being closed
+    await mintTo(provider.connection, payer.payer, closeLpMint, poolSeeds("lp_token_vault", closeLpMint), payer.payer, 1);
+
+    let finalize = (lpTreasury: PublicKey | null) => program.methods
+      .finalizeClosePool(closeLpMint)
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpTokenVault: poolSeeds("lp_token_vault", closeLpMint),
+        lpTokenVaultAuthority: poolSeeds("lp_token_vault_auth", closeLpMint),
+        rewardTokenVault: poolSeeds("reward_token_vault", closeLpMint),
+        rewardTokenVaultAuthority: poolSeeds("reward_token_vault_auth", closeLpMint),
+        treasury,
+        lpTreasury,
+      })
+      .rpc();
+    try {
+      await finalize(null);
+      throw new Error("finalize succeeded without an LP treasury");
+    } catch (err) {
+      if (!(err instanceof anchor.AnchorError) || err.error.errorCode.code !== "LpTreasuryMissing") {
+        throw err;
+      }
+    }
+    const tx = await finalize(lpTreasury);
+    const returned = await provider.connection.getTokenAccountBalance(lpTreasury);
+    assert.equal(returned.value.amount, "1");
+
+    let pools = (await program.account.masterChef.fetch(masterChef.publicKey)).pools as any[];
+    let index = pools.findIndex((pool) => pool.lpToken.equals(closeLpMint));
+    assert.isFalse(pools[index].initialized);
+
+    // the freed slot is the first empty one, so the next pool takes it
+    let nextLpMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    await addPool(nextLpMint);
+    pools = (await program.account.masterChef.fetch(masterChef.publicKey)).pools as any[];
+    assert.isTrue(pools[index].initialized);
+    assert.isTrue(pools[index].lpToken.equals(nextLpMint));
+    console.log(`close pool success, transaction signature is:`, tx);
+  });
+
+  it("audit pool!", async () => {
+    const tx = await program.methods
+      .auditPool(lpMint)
+      .accounts({
+        masterChef: masterChef.publicKey,
+        lpTokenVault,
+        rewardTokenVault,
+      })
+      .rpc({ commitment: "confirmed" });
+    const transaction = await provider.connection.getTransaction(tx, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    for (const event of parser.parseLogs(transaction!.meta!.logMessages!)) {
+      console.log(`${event.name}: ${JSON.stringify(event.data)}`);
+    }
+    console.log(`audit pool success, transaction signature is:`, tx);
+  })
+
+  it("receipt mint!", async () => {
+    let receiptLpMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    let receiptLpTokenAccount = await createAccount(provider.connection, payer.payer, receiptLpMint, payer.publicKey);
+    await mintTo(provider.connection, payer.payer, receiptLpMint, receiptLpTokenAccount, payer.payer, 1000);
+    let pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let poolSeeds = (seed: string) => pda([Buffer.from(seed), receiptLpMint.toBuffer(), masterChef.publicKey.toBuffer()]);
+    let receiptMint = poolSeeds("receipt_mint");
+    let mintAuthority = pda([Buffer.from("mint_authority"), masterChef.publicKey.toBuffer()]);
+    let receiptUserInfo = pda([payer.publicKey.toBuffer(), receiptLpMint.toBuffer(), masterChef.publicKey.toBuffer()]);
+    let receiptTokenAccount = getAssociatedTokenAddressSync(receiptMint, payer.publicKey);
+
+    await program.methods
+      .addPool(rewardMint, receiptLpMint, new anchor.BN(0), new anchor.BN(0))
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint: receiptLpMint,
+        rewardMint,
+        rewardTokenVault: poolSeeds("reward_token_vault"),
+        rewardTokenVaultAuthority: poolSeeds("reward_token_vault_auth"),
+        lpTokenVault: poolSeeds("lp_token_vault"),
+        lpTokenVaultAuthority: poolSeeds("lp_token_vault_auth"),
+      })
+      .rpc();
+    await program.methods
+      .createReceiptMint(receiptLpMint)
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint: receiptLpMint,
+        receiptMint,
+        mintAuthority,
+      })
+      .rpc();
+    await createAccount(provider.connection, payer.payer, receiptMint, payer.publicKey);
+    let recipient = Keypair.generate().publicKey;
+    let recipientReceiptTokenAccount = await createAccount(provider.connection, payer.payer, receiptMint, recipient);
+
+    const tx = await program.methods
+      .deposit(receiptLpMint, new anchor.BN("100"), [])
+      .accounts({
+        masterChef: masterChef.publicKey,
+        lpTokenVault: poolSeeds("lp_token_vault"),
+        user: payer.publicKey,
+        payer: payer.publicKey,
+        userInfo: receiptUserInfo,
+        userLpTokenAccount: receiptLpTokenAccount,
+        allowlistEntry: null,
+        referrerInfo: null,
+        receiptMint,
+        userReceiptTokenAccount: receiptTokenAccount,
+        mintAuthority,
+      })
+      .postInstructions([
+        await program.methods
+          .transferPosition(receiptLpMint, new anchor.BN("40"), [])
+          .accounts({
+            user: payer.publicKey,
+            recipient,
+            masterChef: masterChef.publicKey,
+            userInfo: receiptUserInfo,
+            recipientInfo: pda([recipient.toBuffer(), receiptLpMint.toBuffer(), masterChef.publicKey.toBuffer()]),
+            allowlistEntry: null,
+            receiptMint,
+            userReceiptTokenAccount: receiptTokenAccount,
+            recipientReceiptTokenAccount,
+            mintAuthority,
+          })
+          .instruction(),
+        await program.methods
+          .withdraw(receiptLpMint, new anchor.BN("60"))
+          .accounts({
+            masterChef: masterChef.publicKey,
+            lpTokenVault: poolSeeds("lp_token_vault"),
+            user: payer.publicKey,
+            userInfo: receiptUserInfo,
+            lpTokenVaultAuthority: poolSeeds("lp_token_vault_auth"),
+            userLpTokenAccount: receiptLpTokenAccount,
+            receiptMint,
+            userReceiptTokenAccount: receiptTokenAccount,
+          })
+          .instruction(),
+      ])
+      .rpc();
+    const receipts = await provider.connection.getTokenAccountBalance(receiptTokenAccount);
+    console.log(`receipt balance: ${receipts.value.amount}`);
+    const recipientReceipts = await provider.connection.getTokenAccountBalance(recipientReceiptTokenAccount);
+    console.log(`recipient receipt balance: ${recipientReceipts.value.amount}`);
+    console.log(`receipt mint success, transaction signature is:`, tx);
+  });
+
+  it("batch deposit and claim!", async () => {
+    let pda = (seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    let userRewardTokenAccount = getAssociatedTokenAddressSync(rewardMint, payer.publicKey);
+    let pools = [];
+    for (let i = 0; i < 2; i++) {
+      let batchLpMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+      let batchLpTokenAccount = await createAccount(provider.connection, payer.payer, batchLpMint, payer.publicKey);
+      await mintTo(provider.connection, payer.payer, batchLpMint, batchLpTokenAccount, payer.payer, 1000);
+      let poolSeeds = (seed: string) => pda([Buffer.from(seed), batchLpMint.toBuffer(), masterChef.publicKey.toBuffer()]);
+      let batchUserInfo = pda([payer.publicKey.toBuffer(), batchLpMint.toBuffer(), masterChef.publicKey.toBuffer()]);
+      await program.methods
+        .addPool(rewardMint, batchLpMint, new anchor.BN(0), new anchor.BN(0))
+        .accounts({
+          admin: payer.publicKey,
+          masterChef: masterChef.publicKey,
+          lpMint: batchLpMint,
+          rewardMint,
+          rewardTokenVault: poolSeeds("reward_token_vault"),
+          rewardTokenVaultAuthority: poolSeeds("reward_token_vault_auth"),
+          lpTokenVault: poolSeeds("lp_token_vault"),
+          lpTokenVaultAuthority: poolSeeds("lp_token_vault_auth"),
+        })
+        .rpc();
+      // deposit_many only stakes into existing user infos
+      await program.methods
+        .deposit(batchLpMint, new anchor.BN("0"), [])
+        .accounts({
+          masterChef: masterChef.publicKey,
+          lpTokenVault: poolSeeds("lp_token_vault"),
+          user: payer.publicKey,
+          payer: payer.publicKey,
+          userInfo: batchUserInfo,
+          userLpTokenAccount: batchLpTokenAccount,
+          allowlistEntry: null,
+          referrerInfo: null,
+          receiptMint: null,
+          userReceiptTokenAccount: null,
+          mintAuthority: null,
+        })
+        .rpc();
+      pools.push({ lpMint: batchLpMint, lpTokenAccount: batchLpTokenAccount, userInfo: batchUserInfo, poolSeeds });
+    }
+
+    await program.methods
+      .depositMany(pools.map((pool) => pool.lpMint), pools.map(() => new anchor.BN("100")))
+      .accounts({
+        masterChef: masterChef.publicKey,
+        user: payer.publicKey,
+      })
+      .remainingAccounts(pools.flatMap((pool) => [
+        { pubkey: pool.userInfo, isWritable: true, isSigner: false },
+        { pubkey: pool.lpTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: pool.poolSeeds("lp_token_vault"), isWritable: true, isSigner: false },
+      ]))
+      .rpc();
+    const tx = await program.methods
+      .claimAll(pools.map((pool) => pool.lpMint))
+      .accounts({
+        masterChef: masterChef.publicKey,
+        user: payer.publicKey,
+      })
+      .remainingAccounts(pools.flatMap((pool) => [
+        { pubkey: pool.userInfo, isWritable: true, isSigner: false },
+        { pubkey: userRewardTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: pool.poolSeeds("reward_token_vault"), isWritable: true, isSigner: false },
+        { pubkey: pool.poolSeeds("reward_token_vault_auth"), isWritable: false, isSigner: false },
+      ]))
+      .rpc();
+    for (const pool of pools) {
+      const userInfo = await program.account.userInfo.fetch(pool.userInfo);
+      console.log(`batch user amount: ${userInfo.amount}`);
+    }
+    console.log(`batch deposit and claim success, transaction signature is:`, tx);
+  });
+
 });
@@ -0,0 +1,147 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, Wallet } from "@coral-xyz/anchor";
+import { MasterChef } from "../target/types/master_chef";
+import { MockVault } from "../target/types/mock_vault";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync, createMint, mintTo, createAccount } from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("mock-vault", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const payer = provider.wallet as Wallet;
+  const program = anchor.workspace.MasterChef as Program<MasterChef>;
+  const vaultProgram = anchor.workspace.MockVault as Program<MockVault>;
+
+  let masterChef = Keypair.generate();
+  let rewardMint: PublicKey;
+  let lpMint: PublicKey;
+  let userLpTokenAccount: PublicKey;
+  let vault: PublicKey;
+  let vaultLpTokenAccount: PublicKey;
+  let vaultUserInfo: PublicKey;
+  let depositor: PublicKey;
+
+  let poolAddress = (seed: string) => PublicKey.findProgramAddressSync(
+    [Buffer.from(seed), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+    program.programId
+  )[0];
+
+  let masterChefAccounts = () => ({
+    masterChef: masterChef.publicKey,
+    vaultUserInfo,
+    lpTokenVault: poolAddress("lp_token_vault"),
+    masterChefProgram: program.programId,
+  });
+
+  before(async () => {
+    rewardMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    lpMint = await createMint(provider.connection, payer.payer, payer.publicKey, payer.publicKey, 9);
+    userLpTokenAccount = await createAccount(provider.connection, payer.payer, lpMint, payer.publicKey);
+    await mintTo(provider.connection, payer.payer, lpMint, userLpTokenAccount, payer.payer, 1000);
+
+    [vault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), masterChef.publicKey.toBuffer(), lpMint.toBuffer()],
+      vaultProgram.programId
+    );
+    vaultLpTokenAccount = getAssociatedTokenAddressSync(lpMint, vault, true);
+    [vaultUserInfo] = PublicKey.findProgramAddressSync(
+      [vault.toBuffer(), lpMint.toBuffer(), masterChef.publicKey.toBuffer()],
+      program.programId
+    );
+    [depositor] = PublicKey.findProgramAddressSync(
+      [Buffer.from("depositor"), vault.toBuffer(), payer.publicKey.toBuffer()],
+      vaultProgram.programId
+    );
+
+    await program.methods
+      .initialize()
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+      })
+      .signers([masterChef])
+      .rpc();
+    await program.methods
+      .addPool(rewardMint, lpMint, new anchor.BN(1), new anchor.BN(0))
+      .accounts({
+        admin: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint,
+        rewardMint,
+        rewardTokenVault: poolAddress("reward_token_vault"),
+        rewardTokenVaultAuthority: poolAddress("reward_token_vault_auth"),
+        lpTokenVault: poolAddress("lp_token_vault"),
+        lpTokenVaultAuthority: poolAddress("lp_token_vault_auth"),
+      })
+      .rpc();
+    await mintTo(provider.connection, payer.payer, rewardMint, poolAddress("reward_token_vault"), payer.publicKey, 20000000);
+  });
+
+  it("initialize vault!", async () => {
+    const tx = await vaultProgram.methods
+      .initialize()
+      .accounts({
+        payer: payer.publicKey,
+        masterChef: masterChef.publicKey,
+        lpMint,
+        vault,
+        vaultLpTokenAccount,
+      })
+      .rpc();
+    console.log(`initialize vault success, transaction signature is:`, tx);
+  });
+
+  it("deposit through vault!", async () => {
+    const tx = await vaultProgram.methods
+      .deposit(new anchor.BN("300"))
+      .accounts({
+        ...masterChefAccounts(),
+        user: payer.publicKey,
+        vault,
+        depositor,
+        userLpTokenAccount,
+        vaultLpTokenAccount,
+      })
+      .rpc();
+    const userInfo = await program.account.userInfo.fetch(vaultUserInfo);
+    assert.equal(userInfo.amount.toNumber(), 300);
+    console.log(`deposit through vault success, transaction signature is:`, tx);
+  });
+
+  it("harvest!", async () => {
+    const tx = await vaultProgram.methods
+      .harvest()
+      .accounts({
+        payer: payer.publicKey,
+        vault,
+        masterChef: masterChef.publicKey,
+        rewardMint,
+        vaultRewardTokenAccount: getAssociatedTokenAddressSync(rewardMint, vault, true),
+        vaultUserInfo,
+        rewardTokenVault: poolAddress("reward_token_vault"),
+        rewardTokenVaultAuthority: poolAddress("reward_token_vault_auth"),
+        masterChefProgram: program.programId,
+      })
+      .rpc();
+    console.log(`harvest success, transaction signature is:`, tx);
+  });
+
+  it("withdraw through vault!", async () => {
+    const tx = await vaultProgram.methods
+      .withdraw(new anchor.BN("300"))
+      .accounts({
+        ...masterChefAccounts(),
+        user: payer.publicKey,
+        vault,
+        depositor,
+        userLpTokenAccount,
+        vaultLpTokenAccount,
+        lpTokenVaultAuthority: poolAddress("lp_token_vault_auth"),
+      })
+      .rpc();
+    const userInfo = await program.account.userInfo.fetch(vaultUserInfo);
+    assert.equal(userInfo.amount.toNumber(), 0);
+    console.log(`withdraw through vault success, transaction signature is:`, tx);
+  });
+});